@@ -0,0 +1,68 @@
+//! Compares the throughput of hash map and range lookups.
+//!
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use unicode_font::Variant;
+
+const ITERATIONS: usize = 20;
+
+/// Runs the conversion several times, printing the median throughput.
+fn bench(name: &str, input: &str, convert: impl Fn(&str) -> String) {
+    let mut times: Vec<Duration> = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(convert(black_box(input)));
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+    let median = times[ITERATIONS / 2];
+    let throughput = input.len() as f64 / median.as_secs_f64() / (1024.0 * 1024.0);
+    println!("{name:<32} {:>10.2?} {throughput:>10.1} MiB/s", median);
+}
+
+fn with_map(s: &str, variant: Variant) -> String {
+    s.chars()
+        .map(|c| {
+            let plain = *unicode_font::try_as_plain(&c).unwrap_or(&c);
+            *variant.try_as(&plain).unwrap_or(&c)
+        })
+        .collect()
+}
+
+fn with_ranges(s: &str, variant: Variant) -> String {
+    s.chars()
+        .map(|c| {
+            let plain = Variant::Plain.convert(c).unwrap_or(c);
+            variant.convert(plain).unwrap_or(c)
+        })
+        .collect()
+}
+
+fn main() {
+    let sentence = "The quick brown fox jumps over the lazy dog, 0123456789 times! ";
+    let ascii = sentence.repeat(1 << 14);
+    let mixed: String = [
+        Variant::Bold,
+        Variant::Italic,
+        Variant::Fraktur,
+        Variant::Plain,
+        Variant::Circled,
+    ]
+    .iter()
+    .map(|variant| with_ranges(&sentence.repeat(1 << 12), *variant))
+    .collect();
+
+    for (input_name, input) in [("ascii", &ascii), ("mixed", &mixed)] {
+        for variant in [Variant::Bold, Variant::Plain] {
+            bench(&format!("{input_name} to {variant}, map"), input, |s| {
+                with_map(s, variant)
+            });
+            bench(&format!("{input_name} to {variant}, ranges"), input, |s| {
+                with_ranges(s, variant)
+            });
+        }
+    }
+}
@@ -1,50 +1,137 @@
 //! Builder script for the crate `unicode_font`.
 //! It handles turning Unicode data and extensions into rust code.
 //!
-//! To update the code, update `ÙNIDATA` and `EXTENSION` as needed.
+//! Running it without arguments generates the code from the files in `unidata`, offline.
+//! `UnicodeData.txt` must match the version and SHA-256 pinned in `Version.txt`.
+//! `builder check` generates the code in memory and fails if the checked-in files differ.
+//!
+//! To update the code, edit `EXTENSION` as needed, or run `builder download <VERSION>`,
+//! built with the feature `download`, to pin another version of the Unicode standard, then run the builder.
 
 use builder::prelude::*;
 
 use anyhow::Context;
-use std::collections::HashMap;
-use std::io::Read;
-use std::io::Write;
-
-const UNIDATA: &str = include_str!("../../unidata/UnicodeData.txt");
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
+const UNIDATA_PATH: &str = "../unidata/UnicodeData.txt";
+const PIN_PATH: &str = "../unidata/Version.txt";
 const VARIANTS_PATH: &str = "../unidata/Variants.txt";
 const EXTENSION_PATH: &str = "../unidata/Extension.txt";
 const MAPS_BASE_PATH: &str = "../src/";
 
+const USAGE: &str = "usage: builder [check | download [VERSION]]";
+
 fn main() -> anyhow::Result<()> {
-    update_standard()?;
-    extract_from_standard().context("failed extracting data from standard")?;
-    clean_extension().context("failed cleaing extension file")?;
-    standard::write_phf_maps_standard().context("failed writing standard maps")?;
-    extension::write_phf_maps_extension().context("failed writing extension maps")?;
-    Ok(())
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        [] => generate(),
+        ["check"] => check(),
+        #[cfg(feature = "download")]
+        ["download"] => pin::download(None).context("failed downloading Unicode data"),
+        #[cfg(feature = "download")]
+        ["download", version] => pin::download(Some(version)).context("failed downloading Unicode data"),
+        #[cfg(not(feature = "download"))]
+        ["download", ..] => anyhow::bail!("downloading needs the feature `download`, run `cargo run --features download -- download`"),
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
+/// Content of the files of variants the code is generated from.
+pub struct Inputs {
+    pub pin: pin::Pin,
+    /// `Variants.txt`, extracted from the standard.
+    pub variants: String,
+    /// `Extension.txt`, cleaned from the variants already in the standard.
+    pub extension: String,
+}
+
+impl Inputs {
+    /// Extracts the variants of the pinned standard and cleans the extension.
+    fn new() -> anyhow::Result<Self> {
+        let pin = pin::read()?;
+        let unidata = pin::verified_unidata(&pin).context("failed verifying Unicode data")?;
+        let variants = extract_from_standard(&unidata).context("failed extracting data from standard")?;
+        let extension = std::fs::read_to_string(EXTENSION_PATH).context(format!("failed reading {EXTENSION_PATH}"))?;
+        let extension = clean_extension(&variants, &extension).context("failed cleaing extension file")?;
+        Ok(Inputs {
+            pin,
+            variants,
+            extension,
+        })
+    }
+
+    /// Reader of the variants in the standard.
+    fn standard(&self) -> csv::Reader<&[u8]> {
+        reader(&self.variants)
+    }
+
+    /// Reader of the variants in our extension.
+    fn extension(&self) -> csv::Reader<&[u8]> {
+        reader(&self.extension)
+    }
 }
 
+/// Generated files and their content.
+pub type Files = BTreeMap<PathBuf, String>;
+
+/// Generates the content of every file written by the builder.
+fn generated_files(inputs: &Inputs) -> anyhow::Result<Files> {
+    let mut files = Files::new();
+    files.insert(PathBuf::from(VARIANTS_PATH), inputs.variants.clone());
+    files.insert(PathBuf::from(EXTENSION_PATH), inputs.extension.clone());
+    standard::write_phf_maps_standard(inputs, &mut files).context("failed writing standard maps")?;
+    extension::write_phf_maps_extension(inputs, &mut files).context("failed writing extension maps")?;
+    ranges::write_ranges(inputs, &mut files).context("failed writing range tables")?;
+    ranges::write_extension_only(inputs, &mut files).context("failed writing extension-only tables")?;
+    pin::write_unicode_version(inputs, &mut files);
+    Ok(files)
+}
 
-fn update_standard() -> anyhow::Result<()> {
-    let url = "https://www.unicode.org/Public/UCD/latest/ucd/UnicodeData.txt";
-    let standard = reqwest::blocking::get(url)?.text()?;
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(UNIDATA)
-        .context(format!("failed writing updated UNICODE standard"))?;
-    file.write_all(standard.as_bytes())?;
+/// Generates the code from the files in `unidata`.
+fn generate() -> anyhow::Result<()> {
+    let inputs = Inputs::new()?;
+    for (path, content) in generated_files(&inputs)? {
+        std::fs::write(&path, content).context(format!("failed writing {}", path.display()))?;
+    }
+    features::print_size_report(&inputs).context("failed reporting feature sizes")?;
     Ok(())
 }
 
-/// Writes the data as a map in the location `path`.
+/// Prints a diff of every checked-in file that differs from the generated one.
+///
+/// # Errors
 ///
-/// # Errors 
-/// 
-/// If we could not wirte to `path`.
-pub fn write_map(data: &[(String, String)], path: &std::path::Path) -> anyhow::Result<()> {
+/// If any file differs, for example after editing `Extension.txt` without running the builder.
+fn check() -> anyhow::Result<()> {
+    let inputs = Inputs::new()?;
+    let mut stale = Vec::new();
+    for (path, generated) in generated_files(&inputs)? {
+        let name = path.strip_prefix("..").unwrap_or(&path).display().to_string();
+        let checked_in = match std::fs::read_to_string(&path) {
+            Ok(checked_in) => checked_in,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).context(format!("failed reading {name}")),
+        };
+        if checked_in != generated {
+            let diff = similar::TextDiff::from_lines(&checked_in, &generated);
+            print!("{}", diff.unified_diff().header(&format!("a/{name}"), &format!("b/{name}")));
+            stale.push(name);
+        }
+    }
+    if !stale.is_empty() {
+        anyhow::bail!(
+            "{} generated files are stale: {}. Run the builder to update them.",
+            stale.len(),
+            stale.join(", ")
+        );
+    }
+    println!("Generated files are up to date.");
+    Ok(())
+}
+
+/// Writes the data as a map in the location `path`.
+pub fn write_map(data: &[(String, String)], path: PathBuf, files: &mut Files) {
     let mut out = String::new();
     out += "// Do not edit this file! It is generated by a script.\n";
     out += "phf::phf_map! {\n";
@@ -55,20 +142,11 @@ pub fn write_map(data: &[(String, String)], path: &std::path::Path) -> anyhow::R
         out += &format!("    '\\u{{{input}}}' => '\\u{{{output}}}',\n");
     }
     out += "}";
-
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
-        .context(format!("failed writing map on path {}", path.display()))?;
-    file.write_all(out.as_bytes())?;
-
-    Ok(())
+    files.insert(path, out);
 }
 
 pub mod standard {
-	use super::{write_map, MAPS_BASE_PATH, VARIANTS_PATH};
+	use super::{write_map, Files, Inputs, MAPS_BASE_PATH};
 
     use std::str::FromStr;
     use anyhow::Context;
@@ -78,29 +156,28 @@ pub mod standard {
     use heck::ToSnakeCase;
 
     /// Write phf maps based on the UNICODE standard only.
-    pub fn write_phf_maps_standard() -> anyhow::Result<()> {
+    pub fn write_phf_maps_standard(inputs: &Inputs, files: &mut Files) -> anyhow::Result<()> {
         for variant in Variant::iter() {
-            let rdr = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .comment(Some(b'#'))
-                .delimiter(b';')
-                .from_path(VARIANTS_PATH)
-                .context("failed reading variants file")?;
-            write_map_standard(rdr, variant)?;
+            write_map_standard(inputs, variant, files)?;
         }
         Ok(())
     }
 
     /// Write a standard map. 
-    fn write_map_standard(rdr: csv::Reader<std::fs::File>, variant: Variant) -> anyhow::Result<()> {
-        let data = get_data(rdr, variant)?;
+    fn write_map_standard(inputs: &Inputs, variant: Variant, files: &mut Files) -> anyhow::Result<()> {
+        let data = get_data(inputs.standard(), variant)?;
         let path: PathBuf = [MAPS_BASE_PATH, &format!("{}", variant).to_snake_case()].iter().collect();
-        write_map(&data, &path)?;
+        if variant == Variant::Plain {
+            let owners = super::features::owners(|variant| get_data(inputs.standard(), variant))?;
+            super::features::write_plain_map(&data, &owners, path, files);
+        } else {
+            write_map(&data, path, files);
+        }
         Ok(())
     }
 
     pub fn get_data(
-        mut rdr: csv::Reader<std::fs::File>,
+        mut rdr: csv::Reader<&[u8]>,
         variant: Variant,
     ) -> anyhow::Result<Vec<(String, String)>> {
         let mut data = Vec::new();
@@ -265,48 +342,38 @@ pub mod standard {
 }
 
 pub mod extension {
-    use super::{write_map, EXTENSION_PATH, MAPS_BASE_PATH, VARIANTS_PATH};
+    use super::{write_map, Files, Inputs, MAPS_BASE_PATH};
 
-    use anyhow::Context;
     use builder::prelude::*;
     use std::path::PathBuf;
     use strum::IntoEnumIterator;
     use heck::ToSnakeCase;
 
     /// Write phf maps based on both the UNICODE standard and our extension.
-    pub fn write_phf_maps_extension() -> anyhow::Result<()> {
+    pub fn write_phf_maps_extension(inputs: &Inputs, files: &mut Files) -> anyhow::Result<()> {
         for variant in Variant::iter() {
-            let rdr_standard = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .comment(Some(b'#'))
-                .delimiter(b';')
-                .from_path(VARIANTS_PATH)
-                .context("failed reading variants file")?;
-            let rdr_extension = csv::ReaderBuilder::new()
-                .has_headers(false)
-                .comment(Some(b'#'))
-                .delimiter(b';')
-                .from_path(EXTENSION_PATH)
-                .context("failed reading extension file")?;
-            write_map_extension(rdr_standard, rdr_extension, variant)?;
+            write_map_extension(inputs, variant, files)?;
         }
         Ok(())
     }
 
-    fn write_map_extension(
-        rdr_standard: csv::Reader<std::fs::File>,
-        rdr_extension: csv::Reader<std::fs::File>,
-        variant: Variant,
-    ) -> anyhow::Result<()> {
-        let data = get_data_extension(rdr_standard, rdr_extension, variant)?;
+    fn write_map_extension(inputs: &Inputs, variant: Variant, files: &mut Files) -> anyhow::Result<()> {
+        let data = get_data_extension(inputs.standard(), inputs.extension(), variant)?;
         let path: PathBuf = [MAPS_BASE_PATH, &format!("{}.extension", variant.to_string().to_snake_case())].iter().collect();
-        write_map(&data, &path)?;
+        if variant == Variant::Plain {
+            let owners = super::features::owners(|variant| {
+                get_data_extension(inputs.standard(), inputs.extension(), variant)
+            })?;
+            super::features::write_plain_map(&data, &owners, path, files);
+        } else {
+            write_map(&data, path, files);
+        }
         Ok(())
     }
 
     pub fn get_data_extension(
-        rdr_standard: csv::Reader<std::fs::File>,
-        rdr: csv::Reader<std::fs::File>,
+        rdr_standard: csv::Reader<&[u8]>,
+        rdr: csv::Reader<&[u8]>,
         variant: Variant,
     ) -> anyhow::Result<Vec<(String, String)>> {
         let mut data = super::standard::get_data(rdr_standard, variant)?;
@@ -315,25 +382,443 @@ pub mod extension {
     }
 }
 
-/// Cleans the extension of variants from those already included in the unicode standard.
-fn clean_extension() -> anyhow::Result<()> {
-    let mut rdr = csv::ReaderBuilder::new()
+pub mod ranges {
+    use super::{Files, Inputs, MAPS_BASE_PATH};
+
+    use builder::prelude::*;
+    use heck::ToSnakeCase;
+    use std::collections::HashSet;
+    use strum::IntoEnumIterator;
+
+    /// Write range tables based on the UNICODE standard, and on both the standard and our extension.
+    ///
+    /// They contain the same data as the phf maps, compressed into runs of consecutive
+    /// characters mapped to consecutive characters.
+    pub fn write_ranges(inputs: &Inputs, files: &mut Files) -> anyhow::Result<()> {
+        let header = "// Do not edit this file! It is generated by a script.\n\
+            // Each entry maps the characters from `first` to `last` to consecutive characters from `target`.\n\
+            // Tables are only filled when the cargo feature of their variant is enabled.\n";
+        let mut standard = String::from(header);
+        let mut extension = String::from(header);
+        let standard_data = |variant| super::standard::get_data(inputs.standard(), variant);
+        let extension_data = |variant| {
+            super::extension::get_data_extension(inputs.standard(), inputs.extension(), variant)
+        };
+        let standard_owners = super::features::owners(standard_data)?;
+        let extension_owners = super::features::owners(extension_data)?;
+        for variant in Variant::iter() {
+            if variant == Variant::Plain {
+                standard += &plain_table(&standard_data(variant)?, &standard_owners)?;
+                extension += &plain_table(&extension_data(variant)?, &extension_owners)?;
+            } else {
+                standard += &table(variant, &standard_data(variant)?)?;
+                extension += &table(variant, &extension_data(variant)?)?;
+            }
+        }
+        files.insert([MAPS_BASE_PATH, "ranges"].iter().collect(), standard);
+        files.insert([MAPS_BASE_PATH, "ranges.extension"].iter().collect(), extension);
+        Ok(())
+    }
+
+    /// Write tables of the characters each variant only supports with our extension.
+    ///
+    /// They explain failures when the extension is turned off, without including its maps.
+    pub fn write_extension_only(inputs: &Inputs, files: &mut Files) -> anyhow::Result<()> {
+        let mut out = String::from(
+            "// Do not edit this file! It is generated by a script.\n\
+            // Each entry contains the characters from `first` to `last`, which are only supported with our extension.\n\
+            // Tables are only filled when the cargo feature of their variant is enabled.\n",
+        );
+        let extension_data = |variant| {
+            super::extension::get_data_extension(inputs.standard(), inputs.extension(), variant)
+        };
+        let owners = super::features::owners(extension_data)?;
+        for variant in Variant::iter() {
+            let standard: HashSet<String> = super::standard::get_data(inputs.standard(), variant)?
+                .into_iter()
+                .map(|(input, _)| input)
+                .collect();
+            let keys: Vec<(String, String)> = extension_data(variant)?
+                .into_iter()
+                .filter(|(input, _)| !standard.contains(input))
+                .map(|(input, _)| (input.clone(), input))
+                .collect();
+            let name = variant.to_string().to_snake_case().to_uppercase();
+            if variant == Variant::Plain {
+                let mut runs_with_cfg = Vec::new();
+                for (features, keys) in super::features::group(&keys, &owners) {
+                    let cfg = super::features::cfg(&features);
+                    for run in runs(&keys)? {
+                        runs_with_cfg.push((run, cfg.clone()));
+                    }
+                }
+                runs_with_cfg.sort();
+                out += &format!("pub const {name}: &[(char, char)] = &[\n");
+                for ((first, last, _), cfg) in runs_with_cfg {
+                    if let Some(cfg) = cfg {
+                        out += &format!("    {cfg}\n");
+                    }
+                    out += &format!("    ('\\u{{{first:04X}}}', '\\u{{{last:04X}}}'),\n");
+                }
+                out += "];\n";
+            } else {
+                let feature = super::features::name(variant);
+                out += &format!("#[cfg(feature = \"{feature}\")]\n");
+                out += &format!("pub const {name}: &[(char, char)] = &[\n");
+                for (first, last, _) in runs(&keys)? {
+                    out += &format!("    ('\\u{{{first:04X}}}', '\\u{{{last:04X}}}'),\n");
+                }
+                out += "];\n";
+                out += &format!("#[cfg(not(feature = \"{feature}\"))]\n");
+                out += &format!("pub const {name}: &[(char, char)] = &[];\n");
+            }
+        }
+        files.insert([MAPS_BASE_PATH, "extension_only"].iter().collect(), out);
+        Ok(())
+    }
+
+    /// Compresses the data into runs of `(first, last, target)` codes.
+    pub fn runs(data: &[(String, String)]) -> anyhow::Result<Vec<(u32, u32, u32)>> {
+        let mut codes = data
+            .iter()
+            .map(|(input, output)| {
+                Ok((u32::from_str_radix(input, 16)?, u32::from_str_radix(output, 16)?))
+            })
+            .collect::<anyhow::Result<Vec<(u32, u32)>>>()?;
+        codes.sort();
+        codes.dedup();
+        let mut runs: Vec<(u32, u32, u32)> = Vec::new();
+        for (input, output) in codes {
+            match runs.last_mut() {
+                Some((first, last, target))
+                    if input == *last + 1 && output == *target + (input - *first) =>
+                {
+                    *last = input;
+                }
+                _ => runs.push((input, input, output)),
+            }
+        }
+        Ok(runs)
+    }
+
+    /// Rust code of the table of a variant, empty if its feature is disabled.
+    fn table(variant: Variant, data: &[(String, String)]) -> anyhow::Result<String> {
+        let feature = super::features::name(variant);
+        let name = variant.to_string().to_snake_case().to_uppercase();
+        let mut out = format!("#[cfg(feature = \"{feature}\")]\n");
+        out += &format!("pub const {name}: &[(char, char, char)] = &[\n");
+        for (first, last, target) in runs(data)? {
+            out += &format!("    ('\\u{{{first:04X}}}', '\\u{{{last:04X}}}', '\\u{{{target:04X}}}'),\n");
+        }
+        out += "];\n";
+        out += &format!("#[cfg(not(feature = \"{feature}\"))]\n");
+        out += &format!("pub const {name}: &[(char, char, char)] = &[];\n");
+        Ok(out)
+    }
+
+    /// Rust code of the table of the plain variant, with each run only included if a variant needs it.
+    fn plain_table(
+        data: &[(String, String)],
+        owners: &super::features::Owners,
+    ) -> anyhow::Result<String> {
+        let mut runs_with_cfg = Vec::new();
+        for (features, data) in super::features::group(data, owners) {
+            let cfg = super::features::cfg(&features);
+            for run in runs(&data)? {
+                runs_with_cfg.push((run, cfg.clone()));
+            }
+        }
+        runs_with_cfg.sort();
+        let mut out = String::from("pub const PLAIN: &[(char, char, char)] = &[\n");
+        for ((first, last, target), cfg) in runs_with_cfg {
+            if let Some(cfg) = cfg {
+                out += &format!("    {cfg}\n");
+            }
+            out += &format!("    ('\\u{{{first:04X}}}', '\\u{{{last:04X}}}', '\\u{{{target:04X}}}'),\n");
+        }
+        out += "];\n";
+        Ok(out)
+    }
+}
+
+pub mod features {
+    use super::{Files, Inputs};
+
+    use builder::prelude::*;
+    use heck::ToSnakeCase;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::path::PathBuf;
+    use strum::IntoEnumIterator;
+
+    /// Features of the variants that need each character of the plain map.
+    pub type Owners = HashMap<String, BTreeSet<String>>;
+
+    /// Name of the cargo feature that includes a variant.
+    pub fn name(variant: Variant) -> String {
+        variant.to_string().to_snake_case()
+    }
+
+    /// Finds which variants need each character of the plain map.
+    ///
+    /// Styled characters are needed by their variant,
+    /// while plain characters are needed by every variant converting them.
+    pub fn owners(
+        data_of: impl Fn(Variant) -> anyhow::Result<Vec<(String, String)>>,
+    ) -> anyhow::Result<Owners> {
+        let mut owners = Owners::new();
+        for variant in Variant::iter().filter(|v| v != &Variant::Plain) {
+            for (input, output) in data_of(variant)? {
+                owners.entry(input).or_default().insert(name(variant));
+                owners.entry(output).or_default().insert(name(variant));
+            }
+        }
+        Ok(owners)
+    }
+
+    /// Groups the entries of the plain map by the features that need them.
+    ///
+    /// Entries needed by no variant are grouped under the empty set.
+    pub fn group(
+        data: &[(String, String)],
+        owners: &Owners,
+    ) -> BTreeMap<BTreeSet<String>, Vec<(String, String)>> {
+        let mut groups: BTreeMap<BTreeSet<String>, Vec<(String, String)>> = BTreeMap::new();
+        for (input, output) in data {
+            let features = owners.get(input).cloned().unwrap_or_default();
+            groups
+                .entry(features)
+                .or_default()
+                .push((input.clone(), output.clone()));
+        }
+        groups
+    }
+
+    /// Attribute including code only if any of the features is enabled.
+    ///
+    /// Returns `None` for the empty set, since that code is always included.
+    pub fn cfg(features: &BTreeSet<String>) -> Option<String> {
+        let features: Vec<String> = features
+            .iter()
+            .map(|feature| format!("feature = \"{feature}\""))
+            .collect();
+        match features.len() {
+            0 => None,
+            1 => Some(format!("#[cfg({})]", features[0])),
+            _ => Some(format!("#[cfg(any({}))]", features.join(", "))),
+        }
+    }
+
+    /// Writes the plain map in the location `path`, including each entry only if a variant needs it.
+    ///
+    /// `phf::phf_map!` does not accept attributes on its entries,
+    /// so entries are collected by a chain of macros, one per group,
+    /// defined depending on the features enabled.
+    pub fn write_plain_map(
+        data: &[(String, String)],
+        owners: &Owners,
+        path: PathBuf,
+        files: &mut Files,
+    ) {
+        let groups = group(data, owners);
+        let mut out = String::new();
+        out += "// Do not edit this file! It is generated by a script.\n";
+        out += "// Entries are only included when the cargo feature of a variant needing them is enabled.\n";
+        out += "{\n";
+        for (i, (features, data)) in groups.iter().enumerate() {
+            let next = i + 1;
+            let mut entries = String::new();
+            for (input, output) in data {
+                entries += &format!("            '\\u{{{input}}}' => '\\u{{{output}}}',\n");
+            }
+            let with_entries = format!(
+                "    macro_rules! entries_{i} {{\n        ($($entries:tt)*) => {{ entries_{next}! {{\n            $($entries)*\n{entries}        }} }};\n    }}\n"
+            );
+            match cfg(features) {
+                None => out += &with_entries,
+                Some(cfg) => {
+                    out += &format!("    {cfg}\n");
+                    out += &with_entries;
+                    out += &format!("    {}\n", cfg.replacen("#[cfg(", "#[cfg(not(", 1).replacen(")]", "))]", 1));
+                    out += &format!(
+                        "    macro_rules! entries_{i} {{\n        ($($entries:tt)*) => {{ entries_{next}! {{ $($entries)* }} }};\n    }}\n"
+                    );
+                }
+            }
+        }
+        out += &format!(
+            "    macro_rules! entries_{} {{\n        ($($entries:tt)*) => {{ phf::phf_map! {{ $($entries)* }} }};\n    }}\n",
+            groups.len()
+        );
+        out += "    entries_0! {}\n";
+        out += "}";
+        files.insert(path, out);
+    }
+
+    /// Prints an estimate of the bytes each feature adds to a binary, with our extension.
+    ///
+    /// A phf map entry takes 8 bytes, plus 8 bytes of displacements for every 5 entries,
+    /// and a range takes 12 bytes.
+    pub fn print_size_report(inputs: &Inputs) -> anyhow::Result<()> {
+        let data_of = |variant| {
+            super::extension::get_data_extension(inputs.standard(), inputs.extension(), variant)
+        };
+        let owners = owners(data_of)?;
+        let plain = group(&data_of(Variant::Plain)?, &owners);
+        let map_bytes = |entries: usize| 8 * entries + 8 * entries.div_ceil(5);
+
+        println!("| feature | entries | plain entries | ranges | bytes |");
+        println!("|---|---:|---:|---:|---:|");
+        let mut total = 0;
+        for variant in Variant::iter().filter(|v| v != &Variant::Plain) {
+            let feature = name(variant);
+            let data = data_of(variant)?;
+            let ranges = super::ranges::runs(&data)?.len();
+            // Entries of the plain map only needed by this variant.
+            let exclusive: Vec<(String, String)> = plain
+                .iter()
+                .filter(|(features, _)| features.len() == 1 && features.contains(&feature))
+                .flat_map(|(_, data)| data.iter().cloned())
+                .collect();
+            let plain_ranges = super::ranges::runs(&exclusive)?.len();
+            let bytes = map_bytes(data.len()) + map_bytes(exclusive.len()) + 12 * (ranges + plain_ranges);
+            total += bytes;
+            println!(
+                "| `{feature}` | {} | {} | {} | {bytes} |",
+                data.len(),
+                exclusive.len(),
+                ranges + plain_ranges
+            );
+        }
+        let shared: usize = plain
+            .iter()
+            .filter(|(features, _)| features.len() != 1)
+            .map(|(_, data)| data.len())
+            .sum();
+        println!("\nPlain entries shared by several variants: {shared}");
+        println!("Estimated total: {total} bytes");
+        Ok(())
+    }
+}
+
+pub mod pin {
+    use super::{Files, Inputs, MAPS_BASE_PATH, PIN_PATH, UNIDATA_PATH};
+
+    use anyhow::Context;
+    use sha2::{Digest, Sha256};
+
+    /// Version of the Unicode standard and SHA-256 of its `UnicodeData.txt`.
+    pub struct Pin {
+        pub version: String,
+        pub sha256: String,
+    }
+
+    /// Reads the pin of `Version.txt`.
+    pub fn read() -> anyhow::Result<Pin> {
+        let content = std::fs::read_to_string(PIN_PATH).context(format!("failed reading {PIN_PATH}"))?;
+        let line = content
+            .lines()
+            .find(|line| !line.starts_with('#') && !line.trim().is_empty())
+            .context(format!("no version pinned in {PIN_PATH}"))?;
+        let (version, sha256) = line
+            .split_once(';')
+            .context(format!("pin {line:?} is not `version;sha256`"))?;
+        Ok(Pin {
+            version: version.to_string(),
+            sha256: sha256.to_string(),
+        })
+    }
+
+    #[cfg(feature = "download")]
+    fn write(pin: &Pin) -> anyhow::Result<()> {
+        let out = format!(
+            "#Do not edit! This file is written by `builder download`.\n\
+            #version;sha256 of UnicodeData.txt\n\
+            {};{}\n",
+            pin.version, pin.sha256
+        );
+        std::fs::write(PIN_PATH, out).context(format!("failed writing {PIN_PATH}"))
+    }
+
+    /// Lowercase hexadecimal SHA-256 of the bytes.
+    pub fn sha256(bytes: &[u8]) -> String {
+        Sha256::digest(bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Reads the vendored `UnicodeData.txt`, checking it matches the pin.
+    pub fn verified_unidata(pin: &Pin) -> anyhow::Result<String> {
+        let unidata = std::fs::read_to_string(UNIDATA_PATH).context(format!("failed reading {UNIDATA_PATH}"))?;
+        let sha256 = sha256(unidata.as_bytes());
+        if sha256 != pin.sha256 {
+            anyhow::bail!(
+                "{UNIDATA_PATH} has SHA-256 {sha256}, but Unicode {} is pinned with {} in {PIN_PATH}. \
+                Run `builder download` to restore it.",
+                pin.version,
+                pin.sha256
+            );
+        }
+        Ok(unidata)
+    }
+
+    /// Downloads `UnicodeData.txt` of the pinned version, or of `version` and pins it.
+    ///
+    /// Downloading the pinned version again fails if the checksum differs.
+    #[cfg(feature = "download")]
+    pub fn download(version: Option<&str>) -> anyhow::Result<()> {
+        let pinned = read()?;
+        let version = version.unwrap_or(&pinned.version);
+        let url = format!("https://www.unicode.org/Public/{version}/ucd/UnicodeData.txt");
+        let unidata = reqwest::blocking::get(&url)?.error_for_status()?.text()?;
+        let sha256 = sha256(unidata.as_bytes());
+        if version == pinned.version && sha256 != pinned.sha256 {
+            anyhow::bail!(
+                "{url} has SHA-256 {sha256}, but {} is pinned in {PIN_PATH}",
+                pinned.sha256
+            );
+        }
+        std::fs::write(UNIDATA_PATH, &unidata).context(format!("failed writing {UNIDATA_PATH}"))?;
+        write(&Pin {
+            version: version.to_string(),
+            sha256: sha256.clone(),
+        })?;
+        println!("Pinned Unicode {version} with SHA-256 {sha256}. Run the builder to update the maps.");
+        Ok(())
+    }
+
+    /// Writes the constant `UNICODE_VERSION` of the crate, recording the inputs of the maps.
+    pub fn write_unicode_version(inputs: &Inputs, files: &mut Files) {
+        let pin = &inputs.pin;
+        let mut out = String::new();
+        out += "// Do not edit this file! It is generated by a script.\n";
+        out += "crate::UnicodeVersion {\n";
+        out += &format!("    version: \"{}\",\n", pin.version);
+        out += &format!("    unicode_data_sha256: \"{}\",\n", pin.sha256);
+        out += &format!("    extension_sha256: \"{}\",\n", sha256(inputs.extension.as_bytes()));
+        out += "}";
+        files.insert([MAPS_BASE_PATH, "unicode_version"].iter().collect(), out);
+    }
+}
+
+/// Reader of the content of a file of variants.
+fn reader(content: &str) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
         .has_headers(false)
         .comment(Some(b'#'))
         .delimiter(b';')
-        .from_path(VARIANTS_PATH)
-        .context("failed reading variants file")?;
+        .from_reader(content.as_bytes())
+}
+
+/// Cleans the extension of variants from those already included in the unicode standard.
+fn clean_extension(variants: &str, extension: &str) -> anyhow::Result<String> {
+    let mut rdr = reader(variants);
     let included_codes = rdr
         .records()
         .map(|record| record.expect("failed to read record"))
         .map(|record| record.get(0).expect("failed to get code").to_string())
         .collect::<Vec<String>>();
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(false)
-        .comment(Some(b'#'))
-        .delimiter(b';')
-        .from_path(EXTENSION_PATH)
-        .context("failed reading extension file")?;
+    let mut rdr = reader(extension);
 
     let mut new_extension = csv::WriterBuilder::new()
         .has_headers(false)
@@ -357,26 +842,18 @@ fn clean_extension() -> anyhow::Result<()> {
     	#It contains variants not included in the Unicode standard.\n\
 		#code;character;name;variant;plain_code;plain_character\n\
     	") + &String::from_utf8(new_extension.into_inner()?)?;
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(EXTENSION_PATH)
-        .context("failed opening extension file")?;
-    file.write_all(new_extension.as_bytes())
-        .context("failed writing to extension file")?;
-    Ok(())
+    Ok(new_extension)
 }
 
-/// Extract information from the UNICODE standard.
-fn extract_from_standard() -> anyhow::Result<()> {
+/// Extract information from the UNICODE standard, as the content of `Variants.txt`.
+fn extract_from_standard(unidata: &str) -> anyhow::Result<String> {
     let mut out = String::new();
     out += "#Do not edit! This file is automatically extracted from Unicode Data.\n";
     out += "#code;character;name;variant;plain_code;plain_character\n";
 
     let mut name_map = HashMap::new();
 
-    for line in UNIDATA.lines() {
+    for line in unidata.lines() {
         let fields: Vec<_> = line.split(";").collect();
         debug_assert_eq!(fields.len(), 15);
         let (code, name, description) = (fields[0], fields[1], fields[5]);
@@ -395,24 +872,9 @@ fn extract_from_standard() -> anyhow::Result<()> {
         }
     }
 
-    let mut file = std::fs::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(VARIANTS_PATH)?;
-    file.write_all(out.as_bytes())?;
-
-    // Append plain variants
-    let mut file = std::fs::OpenOptions::new()
-        .read(true)
-        .append(true)
-        .open(VARIANTS_PATH)?;
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)?;
-
     // Collect plain variants
     let mut v = Vec::new();
-    for line in buf.lines().filter(|line| !line.starts_with("#")) {
+    for line in out.lines().filter(|line| !line.starts_with("#")) {
         let fields: Vec<_> = line.split(";").collect();
         let (plain_code, plain_character) = (fields[4], fields[5]);
         v.push((plain_code, plain_character));
@@ -433,7 +895,7 @@ fn extract_from_standard() -> anyhow::Result<()> {
         );
         plain_codes += &line;
     }
-    file.write_all(plain_codes.as_bytes())?;
+    out += &plain_codes;
 
-    Ok(())
+    Ok(out)
 }
@@ -13,204 +13,36 @@ fn App() -> impl IntoView {
     let (input, set_input) = signal(String::new());
     // Output
 
+    let output = move || {
+        let input = input.get();
+        unicode_font::all_variants(&input)
+            .into_iter()
+            .map(|result| {
+                // Mark characters with no version in the variant
+                let output: String = input
+                    .char_indices()
+                    .zip(result.output.chars())
+                    .map(|((i, _), c)| {
+                        if result.unconverted.iter().any(|error| error.index == i) {
+                            '�'
+                        } else {
+                            c
+                        }
+                    })
+                    .collect();
+                view! {
+                    <tr>
+                    <td>{result.variant.to_string()}</td>
+                    <td>{output}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
     let output = view! {
         <table>
             <tbody>
-            // # Implementation
-            // - See the builder crate for a generator
-            <tr>
-            <td>"arabic_mathematical"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_arabic_mathematical(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"bold"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_bold(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"bold_italic"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_bold_italic(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"bold_fraktur"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_bold_fraktur(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"bold_script"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_bold_script(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"circled"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_circled(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"comma"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_comma(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"double_struck"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_double_struck(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"fraktur"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_fraktur(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"full_stop"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_full_stop(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"fullwidth"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_fullwidth(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"italic"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_italic(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"looped"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_looped(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"monospace"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_monospace(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"negative_circled"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_negative_circled(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"negative_squared"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_negative_squared(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"regional"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_regional(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"segmented"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_segmented(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"other"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_other(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"parenthesized"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_parenthesized(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"sans_serif_bold_italic"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_sans_serif_bold_italic(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"sans_serif_bold"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_sans_serif_bold(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"sans_serif_italic"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_sans_serif_italic(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"sans_serif"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_sans_serif(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"script"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_script(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"small_capital"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_small_capital(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"superscript"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_superscript(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"subscript"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_subscript(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"stretched"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_stretched(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"squared"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_squared(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"tailed"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_tailed(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-            <tr>
-            <td>"wide"</td>
-            <td>{ move || -> String { input.get().chars()
-            .map(|c| unicode_font::try_as_wide(&c).cloned().unwrap_or('�'))
-            .collect() } }</td>
-            </tr>
-
+            {output}
             </tbody>
         </table>
     };
@@ -252,7 +84,8 @@ fn App() -> impl IntoView {
             Plain unicodes can be converted to different font variants using the unicode_font crate."</p>
 
             <h3>"This playground"</h3>
-            <p>"When a (plain) insterted character does not have a font variant, we display �."</p>
+            <p>"When a (plain) insterted character does not have a font variant, we display �. \
+            Variants are sorted by the number of characters they could convert."</p>
             <p>
                 "Use the input "
                 <button on:click=move |_| set_input.set("123-abc-i-ABC-ابج-אדה".to_string())>"123-abc-i-ABC-ابج-אדה"</button>
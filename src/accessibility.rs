@@ -0,0 +1,238 @@
+//! Screen-reader friendly rendering of styled text.
+//!
+//! Screen readers announce `"𝐇𝐞𝐥𝐥𝐨"` as "mathematical bold capital H, ...".
+//! This module converts styled text into something that can be read aloud,
+//! and reports how much of a text would be unreadable.
+//!
+//! # Examples
+//!
+//! ```
+//! use unicode_font::accessibility;
+//! assert_eq!(accessibility::to_spoken("𝐇𝐞𝐥𝐥𝐨 world"), "Hello world");
+//! assert_eq!(accessibility::to_annotated("𝐇𝐞𝐥𝐥𝐨"), "bold: Hello");
+//! assert!(!accessibility::report("𝐇𝐞𝐥𝐥𝐨").is_readable());
+//! ```
+
+use crate::runs::{font_runs, Run};
+use crate::Variant;
+
+/// Converts decorative characters to plain, so that they are read as regular letters.
+///
+/// Superscripts, subscripts, flags and other symbols are kept,
+/// since folding them changes the meaning, as in `x²` or `🇺🇸`.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::accessibility;
+/// assert_eq!(accessibility::to_spoken("𝔵² 🇺🇸"), "x² 🇺🇸");
+/// ```
+pub fn to_spoken(s: &str) -> String {
+    s.chars()
+        .map(|c| match Variant::detect(&c) {
+            Some(variant) if is_decorative(variant) => Variant::Plain.convert(c).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Returns `true` for the letter styles, which screen readers should read as plain letters.
+fn is_decorative(variant: Variant) -> bool {
+    match variant {
+        Variant::ArabicMathematical
+        | Variant::ArabicMathematicalInitial
+        | Variant::Bold
+        | Variant::BoldItalic
+        | Variant::BoldFraktur
+        | Variant::BoldScript
+        | Variant::Circled
+        | Variant::Comma
+        | Variant::DoubleStruck
+        | Variant::DoubleStruckItalic
+        | Variant::Fraktur
+        | Variant::FullStop
+        | Variant::Fullwidth
+        | Variant::Italic
+        | Variant::Looped
+        | Variant::Monospace
+        | Variant::NegativeCircled
+        | Variant::NegativeSquared
+        | Variant::Parenthesized
+        | Variant::SansSerifBoldItalic
+        | Variant::SansSerifBold
+        | Variant::SansSerifItalic
+        | Variant::SansSerif
+        | Variant::MathematicalScript
+        | Variant::Script
+        | Variant::Segmented
+        | Variant::SmallCapital
+        | Variant::Squared
+        | Variant::Stretched
+        | Variant::Tailed
+        | Variant::Wide => true,
+        Variant::Other
+        | Variant::Plain
+        | Variant::Regional
+        | Variant::Subscript
+        | Variant::Superscript => false,
+    }
+}
+
+/// Converts styled text to plain, annotating each styled run with the name of its variant.
+///
+/// If the whole text is styled with one variant, the annotation is a prefix.
+/// Otherwise, every annotated run is wrapped in parentheses.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::accessibility;
+/// assert_eq!(accessibility::to_annotated("𝘏𝘦𝘺 𝘺𝘰𝘶"), "sans-serif italic: Hey you");
+/// assert_eq!(
+///     accessibility::to_annotated("say 𝐡𝐞𝐥𝐥𝐨 𝔱𝔬 me"),
+///     "say (bold: hello) (fraktur: to) me"
+/// );
+/// ```
+pub fn to_annotated(s: &str) -> String {
+    let runs: Vec<Run> = font_runs(s).collect();
+    let whole = runs.iter().filter(|run| run.variant.is_some()).count() == 1;
+    let mut out = String::with_capacity(s.len());
+    for run in runs {
+        match run.variant {
+            Some(variant) if variant != Variant::Plain => {
+                let name = spoken_name(variant);
+                if whole {
+                    out += &format!("{name}: {}", run.plain);
+                } else {
+                    out += &format!("({name}: {})", run.plain);
+                }
+            }
+            _ => out += &run.plain,
+        }
+    }
+    out
+}
+
+/// Name of a variant, as it should be read aloud.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{accessibility, Variant};
+/// assert_eq!(accessibility::spoken_name(Variant::SansSerifBold), "sans-serif bold");
+/// assert_eq!(accessibility::spoken_name(Variant::DoubleStruck), "double-struck");
+/// assert_eq!(accessibility::spoken_name(Variant::FullStop), "full stop");
+/// ```
+pub fn spoken_name(variant: Variant) -> String {
+    let mut name = String::new();
+    for c in variant.to_string().chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push(' ');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name.replace("sans serif", "sans-serif")
+        .replace("double struck", "double-struck")
+}
+
+/// Summary of how readable a text is for screen readers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessibilityReport {
+    /// Number of styled characters.
+    pub styled: usize,
+    /// Number of characters that are not whitespace.
+    pub total: usize,
+    /// Runs of styled text, which screen readers would announce character by character.
+    pub unreadable: Vec<Run>,
+}
+
+impl AccessibilityReport {
+    /// Share of non-whitespace characters that are styled, between `0.0` and `1.0`.
+    pub fn styled_ratio(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.styled as f64 / self.total as f64
+    }
+
+    /// Returns `true` if no part of the text is styled.
+    pub fn is_readable(&self) -> bool {
+        self.unreadable.is_empty()
+    }
+}
+
+/// Reports how much of a text is styled and which parts would be unreadable.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::accessibility;
+/// let report = accessibility::report("Sale: 𝐅𝐑𝐄𝐄 stuff");
+/// assert_eq!(report.styled, 4);
+/// assert_eq!(report.total, 14);
+/// assert_eq!(report.unreadable[0].plain, "FREE");
+/// ```
+pub fn report(s: &str) -> AccessibilityReport {
+    let styled = s
+        .chars()
+        .filter(|c| !matches!(Variant::detect(c), None | Some(Variant::Plain)))
+        .count();
+    let total = s.chars().filter(|c| !c.is_whitespace()).count();
+    let unreadable = font_runs(s)
+        .filter(|run| !matches!(run.variant, None | Some(Variant::Plain)))
+        .collect();
+    AccessibilityReport {
+        styled,
+        total,
+        unreadable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn annotations() {
+        assert_eq!(to_annotated("plain text"), "plain text");
+        assert_eq!(to_annotated(""), "");
+        assert_eq!(to_annotated("𝐚 𝐛"), "bold: a b");
+        assert_eq!(to_annotated("𝔞𝐛"), "(fraktur: a)(bold: b)");
+    }
+
+    #[cfg(all(
+        feature = "extension",
+        feature = "bold",
+        feature = "regional",
+        feature = "subscript",
+        feature = "superscript"
+    ))]
+    #[test]
+    fn flags_and_exponents_are_spoken_as_is() {
+        assert_eq!(to_spoken("🇺🇸 x²"), "🇺🇸 x²");
+        assert_eq!(to_spoken("𝐇₂𝐎 𝐦²"), "H₂O m²");
+    }
+
+    #[test]
+    fn spoken_names_are_lowercase_words() {
+        use strum::IntoEnumIterator;
+        for variant in Variant::iter() {
+            let name = spoken_name(variant);
+            assert!(
+                name.chars()
+                    .all(|c| c.is_lowercase() || c == ' ' || c == '-'),
+                "{name}"
+            );
+        }
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn report_ratio() {
+        assert_eq!(report("𝐚𝐛").styled_ratio(), 1.0);
+        assert_eq!(report("a 𝐛").styled_ratio(), 0.5);
+        assert_eq!(report(" ").styled_ratio(), 0.0);
+        assert!(report("plain").is_readable());
+    }
+}
@@ -0,0 +1,236 @@
+//! Conversion between styled Unicode text and ANSI terminal formatting.
+//!
+//! Many terminal fonts have no glyphs for the
+//! [Mathematical Alphanumeric Symbols](https://www.unicode.org/charts/PDF/U1D400.pdf) block.
+//! [`to_ansi`] folds those characters back to plain and renders their weight and slope
+//! with SGR (Select Graphic Rendition) escape codes instead.
+//! [`from_ansi`] goes in the reverse direction.
+//!
+//! # Examples
+//!
+//! ```
+//! use unicode_font::ansi;
+//! let terminal = ansi::to_ansi("𝐇𝐢 there");
+//! assert_eq!(terminal, "\x1b[1mHi\x1b[22m there");
+//! assert_eq!(ansi::from_ansi(&terminal), "𝐇𝐢 there");
+//! ```
+
+use crate::{StyledText, Variant};
+
+const ESCAPE: char = '\x1b';
+
+/// Renders styled text for terminals.
+///
+/// Characters of mathematical alphanumeric variants are converted to plain.
+/// Runs of bold or italic characters are wrapped in the SGR codes for bold (`1`) and italic (`3`),
+/// and closed with the codes for normal intensity (`22`) and not italic (`23`),
+/// so that other attributes, like colors, are preserved.
+///
+/// | Variant | SGR |
+/// | --- | --- |
+/// | `Bold`, `BoldFraktur`, `BoldScript`, `SansSerifBold` | bold |
+/// | `Italic`, `DoubleStruckItalic`, `SansSerifItalic` | italic |
+/// | `BoldItalic`, `SansSerifBoldItalic` | bold and italic |
+/// | `DoubleStruck`, `Fraktur`, `MathematicalScript`, `Monospace`, `SansSerif`, `Script` | none |
+///
+/// Other characters are left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::ansi;
+/// assert_eq!(ansi::to_ansi("𝑎𝒃"), "\x1b[3ma\x1b[1mb\x1b[22;23m");
+/// assert_eq!(ansi::to_ansi("𝚕𝚜 ⓐ"), "ls ⓐ");
+/// ```
+pub fn to_ansi(s: &str) -> String {
+    render(&StyledText::from_unicode(s))
+}
+
+/// Renders [`StyledText`] for terminals.
+///
+/// See [`to_ansi`] for how variants are rendered.
+pub fn render(text: &StyledText) -> String {
+    let mut out = String::with_capacity(text.text().len());
+    let mut current = Sgr::default();
+    for (c, variant) in text.styled_chars() {
+        let (c, sgr) = match variant {
+            Some(variant) => match Sgr::of(variant) {
+                Some(sgr) => (c, sgr),
+                None => (variant.convert(c).unwrap_or(c), Sgr::default()),
+            },
+            None => (c, Sgr::default()),
+        };
+        current.transition(sgr, &mut out);
+        out.push(c);
+    }
+    current.transition(Sgr::default(), &mut out);
+    out
+}
+
+/// Converts text formatted with SGR escape codes into styled text.
+///
+/// Bold and italic text is converted to [`Variant::Bold`], [`Variant::Italic`]
+/// or [`Variant::BoldItalic`] where possible.
+/// All escape sequences are removed, including colors and cursor movements.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::ansi;
+/// assert_eq!(ansi::from_ansi("\x1b[1;31mred\x1b[0m alert"), "𝐫𝐞𝐝 alert");
+/// assert_eq!(ansi::from_ansi("\x1b[1m\x1b[3mA\x1b[22mB"), "𝑨𝐵");
+/// ```
+pub fn from_ansi(s: &str) -> String {
+    parse(s).to_unicode()
+}
+
+/// Parses text formatted with SGR escape codes into [`StyledText`].
+///
+/// See [`from_ansi`] for how escape codes are interpreted.
+pub fn parse(s: &str) -> StyledText {
+    let mut out = StyledText::default();
+    let mut current = Sgr::default();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ESCAPE {
+            out.push(c.encode_utf8(&mut [0; 4]), current.variant());
+            continue;
+        }
+        if chars.next_if_eq(&'[').is_none() {
+            // Other escape sequences: intermediate bytes up to a final byte
+            while chars.next_if(|c| ('\x20'..='\x2F').contains(c)).is_some() {}
+            chars.next();
+            continue;
+        }
+        // Control Sequence Introducer: parameters up to a final byte
+        let mut parameters = String::new();
+        let mut last = None;
+        for c in chars.by_ref() {
+            if ('\x40'..='\x7E').contains(&c) {
+                last = Some(c);
+                break;
+            }
+            parameters.push(c);
+        }
+        if last == Some('m') {
+            current.apply(&parameters);
+        }
+    }
+    out
+}
+
+/// Graphic rendition attributes that can be expressed with fonts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Sgr {
+    bold: bool,
+    italic: bool,
+}
+
+impl Sgr {
+    /// Attributes of a variant, if it should be rendered with SGR codes.
+    fn of(variant: Variant) -> Option<Self> {
+        let (bold, italic) = match variant {
+            Variant::Bold | Variant::BoldFraktur | Variant::BoldScript | Variant::SansSerifBold => {
+                (true, false)
+            }
+            Variant::Italic | Variant::DoubleStruckItalic | Variant::SansSerifItalic => (false, true),
+            Variant::BoldItalic | Variant::SansSerifBoldItalic => (true, true),
+            Variant::DoubleStruck
+            | Variant::Fraktur
+            | Variant::MathematicalScript
+            | Variant::Monospace
+            | Variant::SansSerif
+            | Variant::Script => (false, false),
+            _ => return None,
+        };
+        Some(Sgr { bold, italic })
+    }
+
+    /// Variant with these attributes.
+    fn variant(&self) -> Option<Variant> {
+        match (self.bold, self.italic) {
+            (true, true) => Some(Variant::BoldItalic),
+            (true, false) => Some(Variant::Bold),
+            (false, true) => Some(Variant::Italic),
+            (false, false) => None,
+        }
+    }
+
+    /// Writes the escape sequence going from `self` to `next` and updates `self`.
+    fn transition(&mut self, next: Self, out: &mut String) {
+        let mut codes = Vec::new();
+        match (self.bold, next.bold) {
+            (false, true) => codes.push("1"),
+            (true, false) => codes.push("22"),
+            _ => (),
+        }
+        match (self.italic, next.italic) {
+            (false, true) => codes.push("3"),
+            (true, false) => codes.push("23"),
+            _ => (),
+        }
+        if !codes.is_empty() {
+            out.push(ESCAPE);
+            out.push('[');
+            out.push_str(&codes.join(";"));
+            out.push('m');
+        }
+        *self = next;
+    }
+
+    /// Applies the parameters of an SGR escape sequence.
+    fn apply(&mut self, parameters: &str) {
+        let mut parameters = parameters.split([';', ':']);
+        while let Some(parameter) = parameters.next() {
+            match parameter {
+                "" | "0" => *self = Sgr::default(),
+                "1" => self.bold = true,
+                "3" => self.italic = true,
+                "22" => self.bold = false,
+                "23" => self.italic = false,
+                // Extended colors carry their own parameters
+                "38" | "48" | "58" => match parameters.next() {
+                    Some("5") => {
+                        parameters.next();
+                    }
+                    Some("2") => {
+                        parameters.nth(2);
+                    }
+                    _ => (),
+                },
+                _ => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "bold_italic", feature = "sans_serif", feature = "sans_serif_bold", feature = "sans_serif_italic", feature = "sans_serif_bold_italic"))]
+    #[test]
+    fn to_ansi_runs() {
+        assert_eq!(to_ansi("plain"), "plain");
+        assert_eq!(to_ansi("𝐚𝐛 𝐜"), "\x1b[1mab\x1b[22m \x1b[1mc\x1b[22m");
+        assert_eq!(to_ansi("𝒂𝐛"), "\x1b[1;3ma\x1b[23mb\x1b[22m");
+        assert_eq!(to_ansi("𝖺𝗯𝘤𝙙"), "a\x1b[1mb\x1b[22;3mc\x1b[1md\x1b[22;23m");
+    }
+
+    #[cfg(all(feature = "bold", feature = "italic", feature = "bold_italic"))]
+    #[test]
+    fn from_ansi_attributes() {
+        assert_eq!(from_ansi("\x1b[3mab\x1b[mc"), "𝑎𝑏c");
+        assert_eq!(from_ansi("\x1b[1m\x1b[3mx\x1b[23my"), "𝒙𝐲");
+        assert_eq!(from_ansi("\x1b[38;5;1ma\x1b[38;2;1;3;3mb"), "ab");
+        assert_eq!(from_ansi("\x1b[2Ka\x1b(Bb"), "ab");
+        assert_eq!(from_ansi("\x1b[1m1 ⓐ 𝑎"), "𝟏 ⓐ 𝑎");
+    }
+
+    #[test]
+    fn roundtrip() {
+        for s in ["𝐁𝐨𝐥𝐝 and 𝑖𝑡𝑎𝑙𝑖𝑐", "𝑩𝒐𝒕𝒉", "plain"] {
+            assert_eq!(from_ansi(&to_ansi(s)), s);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! `chart` subcommand: renders the table of every variant of plain characters.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+use std::process::ExitCode;
+use std::str::FromStr;
+use unicode_font::{Variant, VariantSet};
+
+/// Output format of the chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format {s:?}, expected markdown, html or csv"
+            )),
+        }
+    }
+}
+
+/// Arguments of `chart`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub format: Format,
+    /// Columns of the chart, all styled variants by default.
+    pub variants: VariantSet,
+    /// Plain characters to include, all if empty.
+    pub ranges: Vec<RangeInclusive<char>>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut format = Format::default();
+        let mut variants = None;
+        let mut ranges = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--format" => format = value(arg)?.parse()?,
+                "--variants" => {
+                    variants = Some(
+                        value(arg)?
+                            .split(',')
+                            .map(crate::parse_variant)
+                            .collect::<Result<VariantSet, _>>()?,
+                    )
+                }
+                "--range" => {
+                    for range in value(arg)?.split(',') {
+                        ranges.push(parse_range(range)?);
+                    }
+                }
+                _ => return Err(format!("unknown argument {arg:?}")),
+            }
+        }
+        let variants = variants.unwrap_or_else(|| {
+            let mut all = VariantSet::all();
+            all.remove(Variant::Plain);
+            all
+        });
+        Ok(Args {
+            format,
+            variants,
+            ranges,
+        })
+    }
+}
+
+/// Parses a range like `A-Z`, a single character like `!`,
+/// or the same with code points like `U+0041-U+005A`.
+fn parse_range(s: &str) -> Result<RangeInclusive<char>, String> {
+    let bound = |s: &str| -> Result<char, String> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => s
+                .strip_prefix("U+")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| format!("invalid character {s:?} in range")),
+        }
+    };
+    // The separator is the first `-` after the first bound, so that `--Z` and `-` work.
+    let split = s
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '-')
+        .map(|(i, _)| i)
+        .filter(|&i| i + 1 < s.len());
+    let (start, end) = match split {
+        Some(i) => (bound(&s[..i])?, bound(&s[i + 1..])?),
+        None => (bound(s)?, bound(s)?),
+    };
+    if start > end {
+        return Err(format!("range {s:?} is empty"));
+    }
+    Ok(start..=end)
+}
+
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let chart = Chart::new(&args);
+    let out = match args.format {
+        Format::Markdown => chart.markdown(),
+        Format::Html => chart.html(),
+        Format::Csv => chart.csv(),
+    };
+    crate::write_stdout(&out)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Plain characters and their version in each variant.
+struct Chart {
+    variants: Vec<Variant>,
+    /// Plain character and its version in each variant, if any.
+    rows: Vec<(char, Vec<Option<char>>)>,
+}
+
+impl Chart {
+    fn new(args: &Args) -> Self {
+        let variants: Vec<Variant> = args.variants.iter().collect();
+        let plain: BTreeSet<char> = variants
+            .iter()
+            .flat_map(|variant| variant.map().keys().copied())
+            .filter(|c| args.ranges.is_empty() || args.ranges.iter().any(|r| r.contains(c)))
+            .collect();
+        let rows = plain
+            .into_iter()
+            .map(|c| (c, variants.iter().map(|v| v.convert(c)).collect()))
+            .collect();
+        Chart { variants, rows }
+    }
+
+    /// Header and rows as text cells, with blank cells for missing characters.
+    fn cells(&self) -> Vec<Vec<String>> {
+        let header = std::iter::once("plain".to_string())
+            .chain(self.variants.iter().map(|v| crate::snake_case(*v)))
+            .collect();
+        let rows = self.rows.iter().map(|(plain, styled)| {
+            std::iter::once(plain.to_string())
+                .chain(
+                    styled
+                        .iter()
+                        .map(|c| c.map(String::from).unwrap_or_default()),
+                )
+                .collect()
+        });
+        std::iter::once(header).chain(rows).collect()
+    }
+
+    fn markdown(&self) -> String {
+        let mut out = String::new();
+        for (i, row) in self.cells().iter().enumerate() {
+            let row: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            writeln!(out, "| {} |", row.join(" | ")).expect("writing to a string does not fail");
+            if i == 0 {
+                writeln!(out, "|{}", "---|".repeat(row.len()))
+                    .expect("writing to a string does not fail");
+            }
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let escape = |s: &str| {
+            s.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+        };
+        let cells = self.cells();
+        let mut out = String::from("<table>\n  <thead>\n    <tr>");
+        for cell in &cells[0] {
+            write!(out, "<th>{}</th>", escape(cell)).expect("writing to a string does not fail");
+        }
+        out += "</tr>\n  </thead>\n  <tbody>\n";
+        for row in &cells[1..] {
+            out += "    <tr>";
+            for cell in row {
+                write!(out, "<td>{}</td>", escape(cell))
+                    .expect("writing to a string does not fail");
+            }
+            out += "</tr>\n";
+        }
+        out += "  </tbody>\n</table>\n";
+        out
+    }
+
+    fn csv(&self) -> String {
+        let quote = |s: &String| {
+            if s.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.clone()
+            }
+        };
+        let mut out = String::new();
+        for row in self.cells() {
+            let row: Vec<String> = row.iter().map(quote).collect();
+            writeln!(out, "{}", row.join(",")).expect("writing to a string does not fail");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(parse_range("A-Z"), Ok('A'..='Z'));
+        assert_eq!(parse_range("!"), Ok('!'..='!'));
+        assert_eq!(parse_range("-"), Ok('-'..='-'));
+        assert_eq!(parse_range("--/"), Ok('-'..='/'));
+        assert_eq!(parse_range("U+0041-U+0043"), Ok('A'..='C'));
+        assert!(parse_range("Z-A").is_err());
+        assert!(parse_range("AB").is_err());
+    }
+
+    #[cfg(any(feature = "bold", all(feature = "extension", feature = "squared")))]
+    fn chart(s: &str) -> Chart {
+        Chart::new(&Args::parse(&crate::tests::args(s)).unwrap())
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn formats() {
+        let chart = chart("--variants bold,squared --range a-b");
+        assert_eq!(
+            chart.markdown(),
+            "| plain | bold | squared |\n|---|---|---|\n| a | 𝐚 |  |\n| b | 𝐛 |  |\n"
+        );
+        assert_eq!(chart.csv(), "plain,bold,squared\na,𝐚,\nb,𝐛,\n");
+        assert!(chart
+            .html()
+            .contains("<tr><td>a</td><td>𝐚</td><td></td></tr>"));
+    }
+
+    #[cfg(all(feature = "extension", feature = "squared"))]
+    #[test]
+    fn rows_of_selected_variants() {
+        let chart = chart("--variants squared");
+        assert_eq!(chart.rows.len(), 26);
+        assert!(chart.rows.iter().all(|(c, _)| c.is_ascii_uppercase()));
+    }
+}
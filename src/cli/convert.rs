@@ -0,0 +1,230 @@
+//! `convert` subcommand: converts text to a variant.
+
+use std::fmt::Write as _;
+use std::process::ExitCode;
+use std::str::FromStr;
+use unicode_font::stream::StyledFmtWriter;
+use unicode_font::{ConversionReport, Variant};
+
+/// What to do with characters that can not be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Leave them unchanged.
+    #[default]
+    Keep,
+    /// Replace them with U+FFFD REPLACEMENT CHARACTER.
+    Replace,
+    /// Remove them.
+    Drop,
+    /// Report them and fail.
+    Error,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep" => Ok(Policy::Keep),
+            "replace" => Ok(Policy::Replace),
+            "drop" => Ok(Policy::Drop),
+            "error" => Ok(Policy::Error),
+            _ => Err(format!(
+                "unknown policy {s:?}, expected keep, replace, drop or error"
+            )),
+        }
+    }
+}
+
+/// Arguments of `convert`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub variant: Variant,
+    pub policy: Policy,
+    /// Text to convert, or `None` to read standard input.
+    pub text: Option<String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut variant = None;
+        let mut policy = Policy::default();
+        let mut words = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+            match arg.as_str() {
+                "-v" | "--variant" => variant = Some(crate::parse_variant(value(arg)?)?),
+                "-p" | "--to-plain" => variant = Some(Variant::Plain),
+                "--policy" => policy = value(arg)?.parse()?,
+                "--" => words.extend(args.by_ref().cloned()),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option {arg:?}"))
+                }
+                _ => words.push(arg.clone()),
+            }
+        }
+        let variant = variant.ok_or("missing --variant or --to-plain")?;
+        let text = (!words.is_empty()).then(|| words.join(" "));
+        Ok(Args {
+            variant,
+            policy,
+            text,
+        })
+    }
+}
+
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let input = crate::input_text(args.text.as_deref())?;
+    let conversion = convert(&input, args.variant);
+    if args.policy == Policy::Error {
+        if let Some(report) = &conversion.report {
+            eprint!("{}", position_report(&input, report));
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+    crate::write_stdout(&apply(&conversion, args.policy))?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Text shown for each variant by `--list-variants`.
+const SAMPLE: &str = "Abc 123";
+
+/// Sample of the variant, or its first characters if it has no version of [`SAMPLE`].
+pub fn sample(variant: Variant) -> String {
+    let sample = apply(&convert(SAMPLE, variant), Policy::Drop);
+    if !sample.trim().is_empty() {
+        return sample;
+    }
+    let mut entries: Vec<_> = variant.map().entries().collect();
+    entries.sort();
+    entries
+        .iter()
+        .take(SAMPLE.len())
+        .map(|(_, styled)| **styled)
+        .collect()
+}
+
+/// Conversion of a text, keeping the characters that can not be converted.
+pub struct Conversion<'a> {
+    input: &'a str,
+    /// Converted text, with the same characters as the input where conversion failed.
+    kept: String,
+    pub report: Option<ConversionReport>,
+}
+
+pub fn convert(input: &str, variant: Variant) -> Conversion<'_> {
+    let report = variant.try_as_str(input).err();
+    let mut writer = StyledFmtWriter::new(String::with_capacity(input.len()), variant);
+    writer
+        .write_str(input)
+        .expect("writing to a string does not fail");
+    Conversion {
+        input,
+        kept: writer.into_inner(),
+        report,
+    }
+}
+
+/// Applies the policy to the characters that could not be converted.
+///
+/// [`Policy::Error`] keeps them, since failing is up to the caller.
+pub fn apply(conversion: &Conversion, policy: Policy) -> String {
+    let Some(report) = &conversion.report else {
+        return conversion.kept.clone();
+    };
+    let mut failed = report.errors.iter().map(|error| error.index).peekable();
+    let mut output = String::with_capacity(conversion.kept.len());
+    // Conversion is char by char, so characters of the input and output correspond.
+    for ((index, _), c) in conversion.input.char_indices().zip(conversion.kept.chars()) {
+        if failed.next_if_eq(&index).is_none() {
+            output.push(c);
+            continue;
+        }
+        match policy {
+            Policy::Keep | Policy::Error => output.push(c),
+            Policy::Replace => output.push(char::REPLACEMENT_CHARACTER),
+            Policy::Drop => (),
+        }
+    }
+    output
+}
+
+/// Line and column, starting at one, of a byte index.
+pub fn position(input: &str, index: usize) -> (usize, usize) {
+    let before = &input[..index];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+/// Report of the characters that could not be converted, with their line and column.
+fn position_report(input: &str, report: &ConversionReport) -> String {
+    let mut out = String::new();
+    for error in &report.errors {
+        let (line, column) = position(input, error.index);
+        writeln!(out, "{line}:{column}: {error}").expect("writing to a string does not fail");
+    }
+    if !report.suggestions.is_empty() {
+        let names: Vec<_> = report
+            .suggestions
+            .iter()
+            .map(|v| crate::snake_case(*v))
+            .collect();
+        writeln!(out, "variants that would succeed: {}", names.join(", "))
+            .expect("writing to a string does not fail");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::args;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Args::parse(&args("--variant double-struck --policy drop hi there")),
+            Ok(Args {
+                variant: Variant::DoubleStruck,
+                policy: Policy::Drop,
+                text: Some("hi there".to_string()),
+            })
+        );
+        assert_eq!(
+            Args::parse(&args("-p")),
+            Ok(Args {
+                variant: Variant::Plain,
+                policy: Policy::Keep,
+                text: None,
+            })
+        );
+        assert!(Args::parse(&args("hi")).is_err());
+        assert!(Args::parse(&args("-v bold --policy maybe")).is_err());
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn policies() {
+        let conversion = convert("a€ b", Variant::Bold);
+        assert_eq!(apply(&conversion, Policy::Keep), "𝐚€ 𝐛");
+        assert_eq!(apply(&conversion, Policy::Replace), "𝐚\u{FFFD} 𝐛");
+        assert_eq!(apply(&conversion, Policy::Drop), "𝐚 𝐛");
+        assert!(conversion.report.is_some());
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn positions() {
+        let input = "ok\nno €";
+        let report = convert(input, Variant::Bold).report.unwrap();
+        assert!(position_report(input, &report).starts_with("2:4: "));
+    }
+}
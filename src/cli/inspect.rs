@@ -0,0 +1,384 @@
+//! `inspect` subcommand: describes every character of a text.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::process::ExitCode;
+use std::sync::OnceLock;
+use strum::IntoEnumIterator;
+use unicode_font::Variant;
+
+const UNICODE_DATA: &str = include_str!("../../unidata/UnicodeData.txt");
+const STANDARD_VARIANTS: &str = include_str!("../../unidata/Variants.txt");
+const EXTENSION_VARIANTS: &str = include_str!("../../unidata/Extension.txt");
+
+/// Arguments of `inspect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub json: bool,
+    /// Text to inspect, or `None` to read standard input.
+    pub text: Option<String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut json = false;
+        let mut words = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--json" => json = true,
+                "--" => words.extend(args.by_ref().cloned()),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option {arg:?}"))
+                }
+                _ => words.push(arg.clone()),
+            }
+        }
+        let text = (!words.is_empty()).then(|| words.join(" "));
+        Ok(Args { json, text })
+    }
+}
+
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let input = match args.text {
+        Some(text) => text,
+        None => {
+            let input = crate::input_text(None)?;
+            input.strip_suffix('\n').unwrap_or(&input).to_string()
+        }
+    };
+    let infos: Vec<CharInfo> = input.chars().map(CharInfo::new).collect();
+    let out = if args.json {
+        json(&infos)
+    } else {
+        table(&infos)
+    };
+    crate::write_stdout(&out)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Where the mapping of a character to plain comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The Unicode standard.
+    Standard,
+    /// Our extension.
+    Extension,
+}
+
+impl Source {
+    fn of(c: char) -> Option<Self> {
+        static SOURCES: OnceLock<HashMap<u32, Source>> = OnceLock::new();
+        let sources = SOURCES.get_or_init(|| {
+            let mut sources = HashMap::new();
+            for (data, source) in [
+                (STANDARD_VARIANTS, Source::Standard),
+                (EXTENSION_VARIANTS, Source::Extension),
+            ] {
+                for (code, _) in records(data) {
+                    sources.entry(code).or_insert(source);
+                }
+            }
+            sources
+        });
+        sources.get(&(c as u32)).copied()
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Source::Standard => "standard",
+            Source::Extension => "extension",
+        }
+    }
+}
+
+/// Diagnostics of a character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharInfo {
+    pub char: char,
+    pub name: String,
+    pub variant: Option<Variant>,
+    pub plain: Option<char>,
+    pub source: Option<Source>,
+    /// Other variants of the plain character, except plain itself.
+    pub others: Vec<(Variant, char)>,
+}
+
+impl CharInfo {
+    pub fn new(c: char) -> Self {
+        let variant = Variant::detect(&c);
+        let plain = Variant::Plain.convert(c);
+        let others = plain
+            .map(|plain| {
+                Variant::iter()
+                    .filter(|v| v != &Variant::Plain && Some(*v) != variant)
+                    .filter_map(|v| Some((v, v.convert(plain)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        CharInfo {
+            char: c,
+            name: name(c),
+            variant,
+            plain,
+            source: plain.and(Source::of(c)),
+            others,
+        }
+    }
+
+    fn code_point(&self) -> String {
+        format!("U+{:04X}", self.char as u32)
+    }
+}
+
+/// Code point and fields of the records of a semicolon-separated file, skipping comments.
+fn records(data: &'static str) -> impl Iterator<Item = (u32, Vec<&'static str>)> {
+    data.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(';').collect();
+            Some((u32::from_str_radix(fields[0], 16).ok()?, fields))
+        })
+}
+
+/// Names of the Unicode database.
+struct Names {
+    /// Name and old name of characters listed one by one, sorted by code point.
+    chars: Vec<(u32, &'static str, &'static str)>,
+    /// First and last characters of ranges listed by their bounds, with the name of the range.
+    ranges: Vec<(u32, u32, &'static str)>,
+}
+
+impl Names {
+    /// Names parsed from the database on first use.
+    fn get() -> &'static Self {
+        static NAMES: OnceLock<Names> = OnceLock::new();
+        NAMES.get_or_init(|| {
+            let mut names = Names {
+                chars: Vec::new(),
+                ranges: Vec::new(),
+            };
+            let mut first = None;
+            for (code, fields) in records(UNICODE_DATA) {
+                let name = fields[1];
+                if let Some(range) = name.strip_suffix(", First>") {
+                    first = Some((code, &range[1..]));
+                } else if let (Some((start, range)), true) = (first, name.ends_with(", Last>")) {
+                    names.ranges.push((start, code, range));
+                } else {
+                    names
+                        .chars
+                        .push((code, name, fields.get(10).copied().unwrap_or_default()));
+                }
+            }
+            names
+        })
+    }
+}
+
+/// Unicode name of the character.
+///
+/// Control characters get their old name, and characters in ranges of the database a name
+/// derived from their code point, like `CJK UNIFIED IDEOGRAPH-4E01`.
+fn name(c: char) -> String {
+    let code = c as u32;
+    let names = Names::get();
+    if let Ok(i) = names.chars.binary_search_by_key(&code, |(code, ..)| *code) {
+        return match names.chars[i] {
+            (_, "<control>", old_name) if !old_name.is_empty() => {
+                format!("<control> {old_name}")
+            }
+            (_, name, _) => name.to_string(),
+        };
+    }
+    match names
+        .ranges
+        .iter()
+        .find(|(first, last, _)| (first..=last).contains(&&code))
+    {
+        Some((.., "Hangul Syllable")) => hangul_syllable(code),
+        Some((.., range)) if range.starts_with("CJK Ideograph") => {
+            format!("CJK UNIFIED IDEOGRAPH-{code:04X}")
+        }
+        Some((.., range)) if range.starts_with("Tangut Ideograph") => {
+            format!("TANGUT IDEOGRAPH-{code:04X}")
+        }
+        Some((.., range)) if range.ends_with("Private Use") => format!("<private-use-{code:04X}>"),
+        Some((.., range)) => format!("<{range}>"),
+        None => String::from("<unassigned>"),
+    }
+}
+
+/// Name of a precomposed Hangul syllable, from the names of its jamos.
+fn hangul_syllable(code: u32) -> String {
+    const LEADING: [&str; 19] = [
+        "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T",
+        "P", "H",
+    ];
+    const VOWELS: [&str; 21] = [
+        "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO",
+        "WE", "WI", "YU", "EU", "YI", "I",
+    ];
+    const TRAILING: [&str; 28] = [
+        "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH",
+        "M", "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+    ];
+    let index = (code - 0xAC00) as usize;
+    format!(
+        "HANGUL SYLLABLE {}{}{}",
+        LEADING[index / (21 * 28)],
+        VOWELS[index % (21 * 28) / 28],
+        TRAILING[index % 28]
+    )
+}
+
+fn table(infos: &[CharInfo]) -> String {
+    let rows: Vec<[String; 7]> = infos
+        .iter()
+        .map(|info| {
+            [
+                display_char(info.char),
+                info.code_point(),
+                info.name.clone(),
+                info.variant.map_or("-".into(), crate::snake_case),
+                info.plain.map_or("-".into(), display_char),
+                info.source.map_or("-", |s| s.name()).to_string(),
+                info.others
+                    .iter()
+                    .map(|(v, c)| format!("{} {c}", crate::snake_case(*v)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ]
+        })
+        .collect();
+    let header = [
+        "char",
+        "code",
+        "name",
+        "variant",
+        "plain",
+        "source",
+        "other variants",
+    ]
+    .map(String::from);
+    let mut widths = [0; 7];
+    for row in rows.iter().chain([&header]) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let mut line = String::new();
+        for (cell, width) in row.iter().zip(widths) {
+            write!(line, "{cell:<width$}  ").expect("writing to a string does not fail");
+        }
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+/// Character as shown in the table, with control characters and whitespace escaped.
+fn display_char(c: char) -> String {
+    if c.is_control() || c.is_whitespace() {
+        c.escape_default().to_string()
+    } else {
+        c.to_string()
+    }
+}
+
+fn json(infos: &[CharInfo]) -> String {
+    let optional = |s: Option<String>| s.map_or("null".to_string(), |s| json_string(&s));
+    let objects: Vec<String> = infos
+        .iter()
+        .map(|info| {
+            let others: Vec<String> = info
+                .others
+                .iter()
+                .map(|(v, c)| format!("{}: {}", json_string(&crate::snake_case(*v)), json_string(&c.to_string())))
+                .collect();
+            format!(
+                "  {{\"char\": {}, \"code_point\": {}, \"name\": {}, \"variant\": {}, \"plain\": {}, \"source\": {}, \"other_variants\": {{{}}}}}",
+                json_string(&info.char.to_string()),
+                json_string(&info.code_point()),
+                json_string(&info.name),
+                optional(info.variant.map(crate::snake_case)),
+                optional(info.plain.map(String::from)),
+                optional(info.source.map(|s| s.name().to_string())),
+                others.join(", "),
+            )
+        })
+        .collect();
+    if objects.is_empty() {
+        return String::from("[]\n");
+    }
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/// JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", c as u32).expect("writing to a string does not fail")
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn styled_char() {
+        let info = CharInfo::new('𝔥');
+        assert_eq!(info.name, "MATHEMATICAL FRAKTUR SMALL H");
+        assert_eq!(info.variant, Some(Variant::Fraktur));
+        assert_eq!(info.plain, Some('h'));
+        assert_eq!(info.source, Some(Source::Standard));
+        assert!(info.others.contains(&(Variant::Bold, '𝐡')));
+        assert!(!info.others.iter().any(|(v, _)| v == &Variant::Fraktur));
+    }
+
+    #[test]
+    fn unsupported_chars() {
+        let info = CharInfo::new('€');
+        assert_eq!(info.name, "EURO SIGN");
+        assert_eq!((info.variant, info.plain, info.source), (None, None, None));
+        assert!(info.others.is_empty());
+        assert_eq!(name('\u{4E01}'), "CJK UNIFIED IDEOGRAPH-4E01");
+        assert_eq!(name('\u{20000}'), "CJK UNIFIED IDEOGRAPH-20000");
+        assert_eq!(name('\u{17000}'), "TANGUT IDEOGRAPH-17000");
+        assert_eq!(name('\u{AC00}'), "HANGUL SYLLABLE GA");
+        assert_eq!(name('\u{D4DB}'), "HANGUL SYLLABLE PWILH");
+        assert_eq!(name('\u{E000}'), "<private-use-E000>");
+        assert_eq!(name('\u{0378}'), "<unassigned>");
+        assert_eq!(name('\n'), "<control> LINE FEED (LF)");
+    }
+
+    #[cfg(all(feature = "extension", feature = "superscript"))]
+    #[test]
+    fn extension_source() {
+        assert_eq!(CharInfo::new('\u{2070}').source, Some(Source::Extension)); // ⁰
+    }
+
+    #[test]
+    fn json_output() {
+        let out = json(&[CharInfo::new('"'), CharInfo::new('€')]);
+        assert!(out.contains(r#""char": "\"""#));
+        assert!(out.contains(r#""variant": null"#));
+        assert_eq!(json(&[]), "[]\n");
+    }
+}
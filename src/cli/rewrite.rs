@@ -0,0 +1,310 @@
+//! `fold` and `style` subcommands: rewrite files in place.
+//!
+//! Both convert character by character, so lines of the original and rewritten files correspond.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use unicode_font::{Variant, VariantSet};
+
+/// Lines of context around changes in a diff.
+const CONTEXT: usize = 3;
+
+/// Variants folded by default, those of mathematical alphanumeric symbols.
+///
+/// Others, like superscripts or fullwidth forms, are often meant, as in `m²` or CJK text.
+const MATHEMATICAL: [Variant; 15] = [
+    Variant::Bold,
+    Variant::BoldFraktur,
+    Variant::BoldItalic,
+    Variant::BoldScript,
+    Variant::DoubleStruck,
+    Variant::DoubleStruckItalic,
+    Variant::Fraktur,
+    Variant::Italic,
+    Variant::MathematicalScript,
+    Variant::Monospace,
+    Variant::SansSerif,
+    Variant::SansSerifBold,
+    Variant::SansSerifBoldItalic,
+    Variant::SansSerifItalic,
+    Variant::Script,
+];
+
+/// How characters are rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rewrite {
+    /// Styled characters of these variants are replaced by their plain version.
+    Fold(VariantSet),
+    /// Characters are converted to the variant, keeping those with no version in it.
+    Style(Variant),
+}
+
+impl Rewrite {
+    fn apply(&self, c: char) -> char {
+        match self {
+            Rewrite::Fold(variants) => match Variant::detect(&c) {
+                Some(variant) if variants.contains(variant) => {
+                    Variant::Plain.convert(c).unwrap_or(c)
+                }
+                _ => c,
+            },
+            Rewrite::Style(variant) => {
+                let plain = Variant::Plain.convert(c).unwrap_or(c);
+                variant.convert(plain).unwrap_or(c)
+            }
+        }
+    }
+
+    /// Describes a character that would be rewritten, for `--check`.
+    fn describe(&self, c: char, rewritten: char) -> String {
+        match self {
+            Rewrite::Fold(_) => format!(
+                "styled character {c:?} ({}), plain {rewritten:?}",
+                Variant::detect(&c).map_or(String::new(), crate::snake_case)
+            ),
+            Rewrite::Style(variant) => format!(
+                "character {c:?} is not {}, expected {rewritten:?}",
+                crate::snake_case(*variant)
+            ),
+        }
+    }
+}
+
+/// Arguments of `fold` and `style`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub rewrite: Rewrite,
+    /// Only report the characters that would be rewritten.
+    pub check: bool,
+    /// Print a unified diff instead of writing the files.
+    pub diff: bool,
+    pub files: Vec<PathBuf>,
+}
+
+impl Args {
+    pub fn parse_fold(args: &[String]) -> Result<Self, String> {
+        Self::parse(args, false)
+    }
+
+    pub fn parse_style(args: &[String]) -> Result<Self, String> {
+        Self::parse(args, true)
+    }
+
+    fn parse(args: &[String], style: bool) -> Result<Self, String> {
+        let mut variants = None;
+        let mut variant = None;
+        let mut check = false;
+        let mut diff = false;
+        let mut files = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--check" => check = true,
+                "--diff" => diff = true,
+                "--variants" if !style => {
+                    variants = Some(
+                        value(arg)?
+                            .split(',')
+                            .map(crate::parse_variant)
+                            .collect::<Result<VariantSet, _>>()?,
+                    )
+                }
+                "-v" | "--variant" if style => variant = Some(crate::parse_variant(value(arg)?)?),
+                "--" => files.extend(args.by_ref().map(PathBuf::from)),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option {arg:?}"))
+                }
+                _ => files.push(PathBuf::from(arg)),
+            }
+        }
+        if files.is_empty() {
+            return Err("missing FILE".to_string());
+        }
+        let rewrite = if style {
+            Rewrite::Style(variant.ok_or("missing --variant")?)
+        } else {
+            Rewrite::Fold(variants.unwrap_or_else(|| MATHEMATICAL.into_iter().collect()))
+        };
+        Ok(Args {
+            rewrite,
+            check,
+            diff,
+            files,
+        })
+    }
+}
+
+/// Rewrites the files.
+///
+/// With `--check` or `--diff`, files are left unchanged and the exit status is 1 if any would change.
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let mut changed = false;
+    let mut failed = false;
+    for path in &args.files {
+        let original = match std::fs::read_to_string(path) {
+            Ok(original) => original,
+            Err(e) => {
+                eprintln!("error: failed reading {}: {e}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+        let rewritten: String = original.chars().map(|c| args.rewrite.apply(c)).collect();
+        if rewritten == original {
+            continue;
+        }
+        changed = true;
+        let name = path.display().to_string();
+        if args.check {
+            crate::write_stdout(&check_report(&name, &original, &rewritten, &args.rewrite))?;
+        }
+        if args.diff {
+            crate::write_stdout(&unified_diff(&name, &original, &rewritten))?;
+        }
+        if !args.check && !args.diff {
+            if let Err(e) = std::fs::write(path, rewritten) {
+                eprintln!("error: failed writing {name}: {e}");
+                failed = true;
+            }
+        }
+    }
+    Ok(if failed {
+        ExitCode::from(crate::USAGE_ERROR)
+    } else if changed && (args.check || args.diff) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Lists every character that would be rewritten, as `path:line:column: description`.
+fn check_report(name: &str, original: &str, rewritten: &str, rewrite: &Rewrite) -> String {
+    let mut out = String::new();
+    let (mut line, mut column) = (1, 1);
+    for (c, new) in original.chars().zip(rewritten.chars()) {
+        if c != new {
+            let description = rewrite.describe(c, new);
+            writeln!(out, "{name}:{line}:{column}: {description}")
+                .expect("writing to a string does not fail");
+        }
+        if c == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    out
+}
+
+/// Unified diff of two texts with the same number of lines.
+fn unified_diff(name: &str, original: &str, rewritten: &str) -> String {
+    let old: Vec<&str> = original.split_inclusive('\n').collect();
+    let new: Vec<&str> = rewritten.split_inclusive('\n').collect();
+    let changed: Vec<usize> = (0..old.len()).filter(|&i| old[i] != new[i]).collect();
+
+    // Hunks of changed lines closer than twice the context, with their context.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + CONTEXT + 1).min(old.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = format!("--- a/{name}\n+++ b/{name}\n");
+    for (start, end) in hunks {
+        let len = end - start;
+        writeln!(out, "@@ -{},{len} +{},{len} @@", start + 1, start + 1)
+            .expect("writing to a string does not fail");
+        let mut i = start;
+        while i < end {
+            if old[i] == new[i] {
+                push_line(&mut out, ' ', old[i]);
+                i += 1;
+                continue;
+            }
+            let run_end = (i..end).find(|&j| old[j] == new[j]).unwrap_or(end);
+            for line in &old[i..run_end] {
+                push_line(&mut out, '-', line);
+            }
+            for line in &new[i..run_end] {
+                push_line(&mut out, '+', line);
+            }
+            i = run_end;
+        }
+    }
+    out
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::args;
+
+    #[test]
+    fn parse() {
+        let fold =
+            Args::parse_fold(&args("--check --variants bold,double-struck a.rs b.rs")).unwrap();
+        assert!(fold.check && !fold.diff);
+        assert_eq!(
+            fold.rewrite,
+            Rewrite::Fold([Variant::Bold, Variant::DoubleStruck].into_iter().collect())
+        );
+        assert_eq!(fold.files.len(), 2);
+        let Rewrite::Fold(default) = Args::parse_fold(&args("a.rs")).unwrap().rewrite else {
+            panic!("fold parsed as style");
+        };
+        assert!(default.contains(Variant::Fraktur) && default.contains(Variant::Monospace));
+        assert!(!default.contains(Variant::Superscript) && !default.contains(Variant::Fullwidth));
+        let style = Args::parse_style(&args("--variant bold --diff a.rs")).unwrap();
+        assert_eq!(style.rewrite, Rewrite::Style(Variant::Bold));
+        assert!(Args::parse_fold(&args("--check")).is_err());
+        assert!(Args::parse_style(&args("a.rs")).is_err());
+        assert!(Args::parse_fold(&args("--variant bold a.rs")).is_err());
+    }
+
+    #[cfg(all(feature = "bold", feature = "italic"))]
+    #[test]
+    fn fold_only_selected_variants() {
+        let rewrite = Rewrite::Fold(Variant::Bold.into());
+        let folded: String = "𝐚𝑎a".chars().map(|c| rewrite.apply(c)).collect();
+        assert_eq!(folded, "a𝑎a");
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn check_positions() {
+        let rewrite = Rewrite::Fold(VariantSet::all());
+        let report = check_report("f", "ok\nx 𝐚", "ok\nx a", &rewrite);
+        assert_eq!(report, "f:2:3: styled character '𝐚' (bold), plain 'a'\n");
+    }
+
+    #[test]
+    fn diff_hunks() {
+        let original = "1\n2\n3\n4\n5\n𝐚\n7\n8\n9\n10\n11\n12\n13\n𝐛";
+        let rewritten = "1\n2\n3\n4\n5\na\n7\n8\n9\n10\n11\n12\n13\nb";
+        let diff = unified_diff("f", original, rewritten);
+        assert_eq!(
+            diff,
+            "--- a/f\n+++ b/f\n\
+            @@ -3,7 +3,7 @@\n 3\n 4\n 5\n-𝐚\n+a\n 7\n 8\n 9\n\
+            @@ -11,4 +11,4 @@\n 11\n 12\n 13\n-𝐛\n\\ No newline at end of file\n+b\n\\ No newline at end of file\n"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! `search` subcommand: finds a pattern in files, ignoring fonts.
+
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use unicode_font::fold::find_ignore_font;
+
+/// Start and end of a highlighted match, bold red.
+const HIGHLIGHT: (&str, &str) = ("\x1b[1;31m", "\x1b[0m");
+
+/// When to highlight matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Color {
+    /// Only when writing to a terminal.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Arguments of `search`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub pattern: String,
+    /// Files and directories to search recursively, or standard input if empty.
+    pub paths: Vec<PathBuf>,
+    pub color: Color,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut color = Color::default();
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--color" => {
+                    color = match value(arg)?.as_str() {
+                        "auto" => Color::Auto,
+                        "always" => Color::Always,
+                        "never" => Color::Never,
+                        other => {
+                            return Err(format!(
+                                "unknown color {other:?}, expected auto, always or never"
+                            ))
+                        }
+                    }
+                }
+                "--" => positional.extend(args.by_ref().cloned()),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option {arg:?}"))
+                }
+                _ => positional.push(arg.clone()),
+            }
+        }
+        let mut positional = positional.into_iter();
+        let pattern = positional.next().ok_or("missing PATTERN")?;
+        if pattern.is_empty() {
+            return Err("PATTERN is empty".to_string());
+        }
+        Ok(Args {
+            pattern,
+            paths: positional.map(PathBuf::from).collect(),
+            color,
+        })
+    }
+}
+
+/// Prints every match as `path:line:column: line`, with the match highlighted.
+///
+/// As `grep`, the exit status is 0 if there is a match, 1 if there is none, and 2 on errors.
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let highlight = match args.color {
+        Color::Auto => std::io::stdout().is_terminal(),
+        Color::Always => true,
+        Color::Never => false,
+    };
+    let mut found = false;
+    let mut failed = false;
+    if args.paths.is_empty() {
+        let text = crate::input_text(None)?;
+        let out = search(&text, &args.pattern, "(standard input)", highlight);
+        found |= !out.is_empty();
+        crate::write_stdout(&out)?;
+    }
+    for path in &args.paths {
+        let mut files = Vec::new();
+        failed |= !collect_files(path, &mut files);
+        for file in files {
+            let text = match std::fs::read_to_string(&file) {
+                Ok(text) => text,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    eprintln!("warning: skipped {}: not UTF-8 text", file.display());
+                    failed = true;
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("error: failed reading {}: {e}", file.display());
+                    failed = true;
+                    continue;
+                }
+            };
+            let out = search(&text, &args.pattern, &file.display().to_string(), highlight);
+            found |= !out.is_empty();
+            crate::write_stdout(&out)?;
+        }
+    }
+    Ok(if failed {
+        ExitCode::from(crate::USAGE_ERROR)
+    } else if found {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Files in the path, recursively, in alphabetical order.
+///
+/// Symbolic links to directories are only followed if given as the path, so that cycles end.
+/// Entries that can not be read are reported and skipped; returns whether there were none.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> bool {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return true;
+    }
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("error: failed reading {}: {e}", path.display());
+            return false;
+        }
+    };
+    let mut complete = true;
+    let mut paths = Vec::new();
+    for entry in entries {
+        match entry.and_then(|entry| Ok((entry.path(), entry.file_type()?))) {
+            Ok((path, file_type)) if file_type.is_symlink() && path.is_dir() => {}
+            Ok((path, _)) => paths.push(path),
+            Err(e) => {
+                eprintln!("error: failed reading {}: {e}", path.display());
+                complete = false;
+            }
+        }
+    }
+    paths.sort();
+    for path in paths {
+        complete &= collect_files(&path, files);
+    }
+    complete
+}
+
+/// Lines reporting each match of the pattern in the text.
+fn search(text: &str, pattern: &str, name: &str, highlight: bool) -> String {
+    let mut out = String::new();
+    for (line_index, line) in text.lines().enumerate() {
+        for range in find_ignore_font(line, pattern) {
+            let column = line[..range.start].chars().count() + 1;
+            let (start, end) = if highlight { HIGHLIGHT } else { ("", "") };
+            writeln!(
+                out,
+                "{name}:{}:{column}: {}{start}{}{end}{}",
+                line_index + 1,
+                &line[..range.start],
+                &line[range.clone()],
+                &line[range.end..],
+            )
+            .expect("writing to a string does not fail");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::args;
+
+    #[test]
+    fn parse() {
+        let parsed = Args::parse(&args("--color never admin logs a.txt")).unwrap();
+        assert_eq!(parsed.pattern, "admin");
+        assert_eq!(
+            parsed.paths,
+            vec![PathBuf::from("logs"), PathBuf::from("a.txt")]
+        );
+        assert_eq!(parsed.color, Color::Never);
+        assert!(Args::parse(&args("")).is_err());
+        assert!(Args::parse(&args("--color maybe admin")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directories_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("unicode_font_search_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/a.txt"), "admin").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("sub/cycle")).unwrap();
+        let mut files = Vec::new();
+        let complete = collect_files(&dir, &mut files);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(complete);
+        assert_eq!(files, vec![dir.join("sub/a.txt")]);
+    }
+
+    #[cfg(feature = "sans_serif")]
+    #[test]
+    fn styled_matches() {
+        let text = "hello\nI am 𝖺𝖽𝗆𝗂𝗇, and admin\n";
+        assert_eq!(
+            search(text, "admin", "log", false),
+            "log:2:6: I am 𝖺𝖽𝗆𝗂𝗇, and admin\nlog:2:17: I am 𝖺𝖽𝗆𝗂𝗇, and admin\n"
+        );
+        assert_eq!(
+            search("𝖺𝖽𝗆𝗂𝗇", "admin", "log", true),
+            "log:1:1: \x1b[1;31m𝖺𝖽𝗆𝗂𝗇\x1b[0m\n"
+        );
+        assert_eq!(search(text, "root", "log", false), "");
+    }
+}
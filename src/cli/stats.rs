@@ -0,0 +1,252 @@
+//! `stats` subcommand: summarizes the use of styles in messages, one per line.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use unicode_font::fold::FoldedText;
+use unicode_font::transform::variant_histogram;
+use unicode_font::Variant;
+
+/// Default number of words listed.
+const TOP: usize = 10;
+
+/// Arguments of `stats`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub json: bool,
+    /// Number of most common styled words listed.
+    pub top: usize,
+    /// Files of messages, or standard input if empty.
+    pub files: Vec<PathBuf>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut json = false;
+        let mut top = TOP;
+        let mut files = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+            match arg.as_str() {
+                "--json" => json = true,
+                "--top" => {
+                    let value = value(arg)?;
+                    top = value
+                        .parse()
+                        .map_err(|_| format!("invalid number {value:?} for --top"))?;
+                }
+                "--" => files.extend(args.by_ref().map(PathBuf::from)),
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("unknown option {arg:?}"))
+                }
+                _ => files.push(PathBuf::from(arg)),
+            }
+        }
+        Ok(Args { json, top, files })
+    }
+}
+
+pub fn run(args: Args) -> Result<ExitCode, String> {
+    let mut stats = Stats::default();
+    if args.files.is_empty() {
+        stats.add_text(&crate::input_text(None)?);
+    }
+    for file in &args.files {
+        let text = std::fs::read_to_string(file)
+            .map_err(|e| format!("failed reading {}: {e}", file.display()))?;
+        stats.add_text(&text);
+    }
+    let out = if args.json {
+        stats.json(args.top)
+    } else {
+        stats.table(args.top)
+    };
+    crate::write_stdout(&out)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Style usage of a collection of messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Stats {
+    messages: usize,
+    /// Messages with at least one styled character.
+    styled_messages: usize,
+    /// Characters of each variant, including plain.
+    variants: BTreeMap<Variant, usize>,
+    /// Words with styled characters, folded to plain.
+    styled_words: HashMap<String, usize>,
+}
+
+impl Stats {
+    /// Adds every line of the text as a message.
+    fn add_text(&mut self, text: &str) {
+        for line in text.lines() {
+            self.add_message(line);
+        }
+    }
+
+    fn add_message(&mut self, message: &str) {
+        self.messages += 1;
+        let histogram = variant_histogram(message);
+        if histogram.keys().any(|variant| variant != &Variant::Plain) {
+            self.styled_messages += 1;
+        }
+        for (variant, count) in histogram {
+            *self.variants.entry(variant).or_default() += count;
+        }
+        for word in message.split_whitespace() {
+            let styled = word
+                .chars()
+                .any(|c| Variant::detect(&c).is_some_and(|v| v != Variant::Plain));
+            if !styled {
+                continue;
+            }
+            let folded = FoldedText::new(word);
+            let folded = folded.folded().trim_matches(|c: char| !c.is_alphanumeric());
+            if !folded.is_empty() {
+                *self.styled_words.entry(folded.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    /// Characters with a variant, plain or styled.
+    fn characters(&self) -> usize {
+        self.variants.values().sum()
+    }
+
+    /// Fraction of the characters, or zero if there are none.
+    fn share(&self, count: usize) -> f64 {
+        match self.characters() {
+            0 => 0.0,
+            total => count as f64 / total as f64,
+        }
+    }
+
+    /// Most common styled words, breaking ties alphabetically.
+    fn top_words(&self, top: usize) -> Vec<(&str, usize)> {
+        let mut words: Vec<(&str, usize)> = self
+            .styled_words
+            .iter()
+            .map(|(word, count)| (word.as_str(), *count))
+            .collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        words.truncate(top);
+        words
+    }
+
+    fn table(&self, top: usize) -> String {
+        let styled_share = match self.messages {
+            0 => 0.0,
+            messages => self.styled_messages as f64 / messages as f64,
+        };
+        let mut out = format!(
+            "messages             {}\nmessages with styles {} ({:.1}%)\n",
+            self.messages,
+            self.styled_messages,
+            100.0 * styled_share
+        );
+        writeln!(
+            out,
+            "\n{:<28} {:>10} {:>7}",
+            "variant", "characters", "share"
+        )
+        .expect("writing to a string does not fail");
+        for (variant, count) in &self.variants {
+            let name = crate::snake_case(*variant);
+            let share = 100.0 * self.share(*count);
+            writeln!(out, "{name:<28} {count:>10} {share:>6.1}%")
+                .expect("writing to a string does not fail");
+        }
+        let words = self.top_words(top);
+        if !words.is_empty() {
+            writeln!(out, "\n{:<28} {:>10}", "styled word", "count")
+                .expect("writing to a string does not fail");
+            for (word, count) in words {
+                writeln!(out, "{word:<28} {count:>10}").expect("writing to a string does not fail");
+            }
+        }
+        out
+    }
+
+    fn json(&self, top: usize) -> String {
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|(variant, count)| {
+                format!(
+                    "\"{}\": {{\"characters\": {count}, \"share\": {:.4}}}",
+                    crate::snake_case(*variant),
+                    self.share(*count)
+                )
+            })
+            .collect();
+        let words: Vec<String> = self
+            .top_words(top)
+            .iter()
+            .map(|(word, count)| {
+                format!(
+                    "{{\"word\": {}, \"count\": {count}}}",
+                    crate::cli::inspect::json_string(word)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"messages\": {}, \"styled_messages\": {}, \"characters\": {}, \"variants\": {{{}}}, \"top_styled_words\": [{}]}}\n",
+            self.messages,
+            self.styled_messages,
+            self.characters(),
+            variants.join(", "),
+            words.join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::args;
+
+    #[cfg(feature = "bold")]
+    fn stats(text: &str) -> Stats {
+        let mut stats = Stats::default();
+        stats.add_text(text);
+        stats
+    }
+
+    #[test]
+    fn parse() {
+        let parsed = Args::parse(&args("--json --top 3 a.log")).unwrap();
+        assert!(parsed.json);
+        assert_eq!(parsed.top, 3);
+        assert_eq!(parsed.files, vec![PathBuf::from("a.log")]);
+        assert!(Args::parse(&args("--top many")).is_err());
+    }
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn counts() {
+        let stats = stats("get 𝐟𝐫𝐞𝐞 stuff\n𝔣𝔯𝔢𝔢!\nhello\n");
+        assert_eq!(stats.messages, 3);
+        assert_eq!(stats.styled_messages, 2);
+        assert_eq!(stats.variants[&Variant::Bold], 4);
+        assert_eq!(stats.variants[&Variant::Fraktur], 4);
+        assert_eq!(stats.top_words(10), vec![("free", 2)]);
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn json_output() {
+        assert_eq!(
+            stats("𝐚b").json(10),
+            "{\"messages\": 1, \"styled_messages\": 1, \"characters\": 2, \
+            \"variants\": {\"bold\": {\"characters\": 1, \"share\": 0.5000}, \
+            \"plain\": {\"characters\": 1, \"share\": 0.5000}}, \
+            \"top_styled_words\": [{\"word\": \"ab\", \"count\": 1}]}\n"
+        );
+    }
+}
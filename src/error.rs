@@ -0,0 +1,188 @@
+//! Errors explaining why a text could not be converted.
+//!
+//! Lookups like [`Variant::try_as`] return an [`Option`], which does not tell why a character
+//! was left unchanged. The string APIs report a [`ConversionError`] for every such character instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use unicode_font::{error::Reason, Variant};
+//! let report = Variant::Fraktur.try_as_str("Room 42").unwrap_err();
+//! assert_eq!(report.errors.len(), 2);
+//! assert_eq!(report.errors[0].char, '4');
+//! assert_eq!(report.errors[0].reason, Reason::NoFormInVariant);
+//! assert!(report.suggestions.contains(&Variant::Bold));
+//! ```
+
+use crate::Variant;
+use core::fmt;
+use strum::IntoEnumIterator;
+
+/// Why a character could not be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    /// The character has no plain version, so it is not supported at all.
+    NotInPlainTable,
+    /// The character is supported, but has no version in the variant.
+    NoFormInVariant,
+    /// The conversion is part of our extension, and the `extension` feature is disabled.
+    ExtensionDisabled,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Reason::NotInPlainTable => "the character is not supported",
+            Reason::NoFormInVariant => "the variant has no version of the character",
+            Reason::ExtensionDisabled => "the conversion requires the `extension` feature",
+        })
+    }
+}
+
+/// A character that could not be converted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConversionError {
+    /// Byte index of the character in the input.
+    pub index: usize,
+    /// Character that could not be converted.
+    pub char: char,
+    /// Variant the character was converted to.
+    pub variant: Variant,
+    /// Why the conversion failed.
+    pub reason: Reason,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "can not convert {:?} at byte {} to {}: {}",
+            self.char, self.index, self.variant, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Every character of a text that could not be converted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConversionReport {
+    /// Failed characters, in order of appearance.
+    pub errors: Vec<ConversionError>,
+    /// Other styled variants that can convert the whole text.
+    pub suggestions: Vec<Variant>,
+}
+
+impl ConversionReport {
+    /// Reports the failed characters of a text, suggesting variants that would succeed.
+    pub(crate) fn new(s: &str, errors: Vec<ConversionError>) -> Self {
+        let failed = errors.first().map(|error| error.variant);
+        let suggestions = Variant::iter()
+            .filter(|variant| variant != &Variant::Plain && Some(*variant) != failed)
+            .filter(|variant| {
+                s.chars()
+                    .filter(|c| !c.is_whitespace())
+                    .all(|c| convert_char(c, *variant).is_ok())
+            })
+            .collect();
+        ConversionReport {
+            errors,
+            suggestions,
+        }
+    }
+
+    /// Returns `true` if no character failed.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for error in &self.errors {
+            writeln!(f, "{error}")?;
+        }
+        if !self.suggestions.is_empty() {
+            let names: Vec<_> = self.suggestions.iter().map(|v| v.to_string()).collect();
+            write!(f, "variants that would succeed: {}", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConversionReport {}
+
+/// Converts a character of any variant into the given variant.
+pub(crate) fn convert_char(c: char, variant: Variant) -> Result<char, Reason> {
+    let Some(plain) = Variant::Plain.convert(c) else {
+        return Err(missing_plain(c));
+    };
+    if variant == Variant::Plain {
+        return Ok(plain);
+    }
+    variant
+        .convert(plain)
+        .ok_or_else(|| missing_form(plain, variant))
+}
+
+/// Reason for a character without plain version.
+#[cfg_attr(feature = "extension", allow(unused_variables))]
+pub(crate) fn missing_plain(c: char) -> Reason {
+    #[cfg(not(feature = "extension"))]
+    if crate::extension_only::contains(Variant::Plain.extension_only(), c) {
+        return Reason::ExtensionDisabled;
+    }
+    Reason::NotInPlainTable
+}
+
+/// Reason for a plain character without version in the variant.
+#[cfg_attr(feature = "extension", allow(unused_variables))]
+pub(crate) fn missing_form(plain: char, variant: Variant) -> Reason {
+    #[cfg(not(feature = "extension"))]
+    if crate::extension_only::contains(variant.extension_only(), plain) {
+        return Reason::ExtensionDisabled;
+    }
+    Reason::NoFormInVariant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn reasons() {
+        assert_eq!(convert_char('a', Variant::Bold), Ok('𝐚'));
+        assert_eq!(convert_char('𝔞', Variant::Plain), Ok('a'));
+        assert_eq!(
+            convert_char('€', Variant::Bold),
+            Err(Reason::NotInPlainTable)
+        );
+        assert_eq!(
+            convert_char('1', Variant::Fraktur),
+            Err(Reason::NoFormInVariant)
+        );
+    }
+
+    #[test]
+    fn report_lists_every_failure() {
+        let report = Variant::SmallCapital.try_as_str("hi 12").unwrap_err();
+        let indices: Vec<_> = report.errors.iter().map(|error| error.index).collect();
+        assert_eq!(indices, vec![0, 1, 3, 4]);
+        assert!(!report.suggestions.contains(&Variant::SmallCapital));
+        assert!(report
+            .to_string()
+            .contains("can not convert 'h' at byte 0 to SmallCapital"));
+        assert!(Variant::Bold.try_as_str("").is_ok());
+    }
+
+    #[cfg(all(not(feature = "extension"), feature = "bold", feature = "superscript"))]
+    #[test]
+    fn extension_disabled() {
+        // Superscripts are only in our extension
+        assert_eq!(convert_char('²', Variant::Plain), Err(Reason::ExtensionDisabled));
+        assert_eq!(convert_char('2', Variant::Superscript), Err(Reason::ExtensionDisabled));
+        assert_eq!(convert_char('€', Variant::Plain), Err(Reason::NotInPlainTable));
+        assert_eq!(convert_char('1', Variant::Fraktur), Err(Reason::NoFormInVariant));
+    }
+}
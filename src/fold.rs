@@ -0,0 +1,387 @@
+//! Font folding: converting text to plain while keeping track of positions.
+//!
+//! # Examples
+//!
+//! Highlight a styled occurrence of a plain word.
+//! ```
+//! use unicode_font::fold::find_ignore_font;
+//! let haystack = "Get 𝔣𝔯𝔢𝔢 stuff";
+//! let ranges: Vec<_> = find_ignore_font(haystack, "free").collect();
+//! assert_eq!(ranges, vec![4..20]);
+//! assert_eq!(&haystack[4..20], "𝔣𝔯𝔢𝔢");
+//! ```
+//!
+//! Use display names as keys, regardless of their font.
+//! ```
+//! use std::collections::HashMap;
+//! use unicode_font::fold::FontInsensitive;
+//! let mut scores = HashMap::new();
+//! scores.insert(FontInsensitive("𝐀𝐥𝐢𝐜𝐞"), 1);
+//! assert_eq!(scores.get(&FontInsensitive("Alice")), Some(&1));
+//! ```
+
+use crate::Variant;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+
+/// Text converted to plain, with a map of offsets back to the original text.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::fold::FoldedText;
+/// let text = FoldedText::new("a𝐛c");
+/// assert_eq!(text.folded(), "abc");
+/// assert_eq!(text.to_original(1..3), 1..6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldedText {
+    folded: String,
+    original_len: usize,
+    /// Pairs of folded and original offsets, after every character whose length changed.
+    ///
+    /// Between two checkpoints, characters have the same length in both texts.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl FoldedText {
+    /// Converts every character to plain, when possible.
+    pub fn new(original: &str) -> Self {
+        let mut folded = String::with_capacity(original.len());
+        let mut checkpoints = vec![(0, 0)];
+        for (i, c) in original.char_indices() {
+            let plain = Variant::Plain.convert(c).unwrap_or(c);
+            folded.push(plain);
+            if plain.len_utf8() != c.len_utf8() {
+                checkpoints.push((folded.len(), i + c.len_utf8()));
+            }
+        }
+        FoldedText {
+            folded,
+            original_len: original.len(),
+            checkpoints,
+        }
+    }
+
+    /// Text with every character converted to plain.
+    pub fn folded(&self) -> &str {
+        &self.folded
+    }
+
+    /// Converts a byte range of the folded text into the corresponding range of the original text.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on `char` boundaries of the folded text.
+    pub fn to_original(&self, range: Range<usize>) -> Range<usize> {
+        self.to_original_offset(range.start)..self.to_original_offset(range.end)
+    }
+
+    /// Converts a byte offset of the folded text into the corresponding offset of the original text.
+    ///
+    /// # Panics
+    ///
+    /// If the offset is out of bounds or is not on a `char` boundary of the folded text.
+    pub fn to_original_offset(&self, offset: usize) -> usize {
+        assert!(
+            self.folded.is_char_boundary(offset),
+            "offset {offset} is not a char boundary of the folded text {:?}",
+            self.folded
+        );
+        if offset == self.folded.len() {
+            return self.original_len;
+        }
+        let index = self
+            .checkpoints
+            .partition_point(|&(folded, _)| folded <= offset);
+        let (folded, original) = self.checkpoints[index - 1];
+        original + (offset - folded)
+    }
+}
+
+/// Finds the occurrences of `needle` in `haystack`, ignoring fonts.
+///
+/// Both texts are folded to plain before searching.
+/// Matches do not overlap and are reported as byte ranges in the original `haystack`.
+/// An empty needle matches nothing.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::fold::find_ignore_font;
+/// let ranges: Vec<_> = find_ignore_font("𝐚𝐚a", "aa").collect();
+/// assert_eq!(ranges, vec![0..8]);
+/// ```
+pub fn find_ignore_font(haystack: &str, needle: &str) -> FindIgnoreFont {
+    FindIgnoreFont {
+        haystack: FoldedText::new(haystack),
+        needle: FoldedText::new(needle).folded,
+        position: 0,
+    }
+}
+
+/// Iterator over the occurrences of a needle in a haystack, ignoring fonts.
+///
+/// This struct is created by [`find_ignore_font`].
+#[derive(Debug, Clone)]
+pub struct FindIgnoreFont {
+    haystack: FoldedText,
+    needle: String,
+    position: usize,
+}
+
+impl Iterator for FindIgnoreFont {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.needle.is_empty() {
+            return None;
+        }
+        let start = self.position + self.haystack.folded[self.position..].find(&self.needle)?;
+        self.position = start + self.needle.len();
+        Some(self.haystack.to_original(start..self.position))
+    }
+}
+
+/// Returns `true` if both texts are equal after folding them to plain.
+///
+/// This does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::fold::eq_ignore_font;
+/// assert!(eq_ignore_font("𝐀𝐥𝐢𝐜𝐞", "Alice"));
+/// assert!(!eq_ignore_font("𝐀𝐥𝐢𝐜𝐞", "alice"));
+/// ```
+pub fn eq_ignore_font(a: &str, b: &str) -> bool {
+    folded_chars(a, false).eq(folded_chars(b, false))
+}
+
+/// Compares both texts after folding them to plain.
+///
+/// This does not allocate.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use unicode_font::fold::cmp_ignore_font;
+/// assert_eq!(cmp_ignore_font("𝐁ob", "Alice"), Ordering::Greater);
+/// ```
+pub fn cmp_ignore_font(a: &str, b: &str) -> Ordering {
+    folded_chars(a, false).cmp(folded_chars(b, false))
+}
+
+/// Returns `true` if both texts are equal after folding them to plain and to lowercase.
+///
+/// Case folding is simple: characters whose lowercase version is longer than one `char` are kept.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::fold::eq_ignore_font_and_case;
+/// assert!(eq_ignore_font_and_case("𝐀𝐥𝐢𝐜𝐞", "ALICE"));
+/// ```
+pub fn eq_ignore_font_and_case(a: &str, b: &str) -> bool {
+    folded_chars(a, true).eq(folded_chars(b, true))
+}
+
+/// Compares both texts after folding them to plain and to lowercase.
+///
+/// See [`eq_ignore_font_and_case`] for the case folding used.
+pub fn cmp_ignore_font_and_case(a: &str, b: &str) -> Ordering {
+    folded_chars(a, true).cmp(folded_chars(b, true))
+}
+
+/// Characters of the text folded to plain, and optionally to lowercase.
+fn folded_chars(s: &str, ignore_case: bool) -> impl Iterator<Item = char> + '_ {
+    s.chars().map(move |c| {
+        let plain = Variant::Plain.convert(c).unwrap_or(c);
+        if ignore_case {
+            let mut lowercase = plain.to_lowercase();
+            match (lowercase.next(), lowercase.next()) {
+                (Some(lower), None) => lower,
+                _ => plain,
+            }
+        } else {
+            plain
+        }
+    })
+}
+
+/// Hashes the characters of the text folded to plain, and optionally to lowercase.
+fn hash_folded<H: Hasher>(s: &str, ignore_case: bool, state: &mut H) {
+    for c in folded_chars(s, ignore_case) {
+        c.hash(state);
+    }
+    // Same terminator as `str`, so that `("ab", "c")` and `("a", "bc")` hash differently
+    state.write_u8(0xff);
+}
+
+macro_rules! insensitive {
+    ($(#[$meta: meta])* $name: ident, ignore_case = $ignore_case: literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $name<S>(pub S);
+
+        impl<S> $name<S> {
+            /// Returns the wrapped text.
+            pub fn into_inner(self) -> S {
+                self.0
+            }
+        }
+
+        impl<S: AsRef<str>> PartialEq for $name<S> {
+            fn eq(&self, other: &Self) -> bool {
+                folded_chars(self.0.as_ref(), $ignore_case)
+                    .eq(folded_chars(other.0.as_ref(), $ignore_case))
+            }
+        }
+
+        impl<S: AsRef<str>> Eq for $name<S> {}
+
+        impl<S: AsRef<str>> PartialOrd for $name<S> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<S: AsRef<str>> Ord for $name<S> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                folded_chars(self.0.as_ref(), $ignore_case)
+                    .cmp(folded_chars(other.0.as_ref(), $ignore_case))
+            }
+        }
+
+        impl<S: AsRef<str>> Hash for $name<S> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                hash_folded(self.0.as_ref(), $ignore_case, state)
+            }
+        }
+
+        impl<S: core::fmt::Display> core::fmt::Display for $name<S> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+insensitive!(
+    /// Text that is compared, ordered and hashed ignoring fonts.
+    ///
+    /// Comparisons fold characters to plain on the fly, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::fold::FontInsensitive;
+    /// assert_eq!(FontInsensitive("𝐀𝐥𝐢𝐜𝐞"), FontInsensitive("Alice"));
+    /// assert_ne!(FontInsensitive("𝐀𝐥𝐢𝐜𝐞"), FontInsensitive("alice"));
+    /// ```
+    FontInsensitive,
+    ignore_case = false
+);
+
+insensitive!(
+    /// Text that is compared, ordered and hashed ignoring fonts and case.
+    ///
+    /// See [`eq_ignore_font_and_case`] for the case folding used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::fold::FontCaseInsensitive;
+    /// assert_eq!(FontCaseInsensitive("𝐀𝐥𝐢𝐜𝐞"), FontCaseInsensitive("alice"));
+    /// ```
+    FontCaseInsensitive,
+    ignore_case = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "double_struck", feature = "fraktur", feature = "script"))]
+    #[test]
+    fn offsets_round_trip() {
+        let original = "𝔞 b 𝐜 ℂ ℎ é";
+        let text = FoldedText::new(original);
+        assert_eq!(text.folded(), "a b c C h é");
+        for (i, c) in text.folded().char_indices() {
+            let range = text.to_original(i..i + c.len_utf8());
+            let styled = original[range].chars().next().unwrap();
+            assert_eq!(crate::try_as_plain(&styled).unwrap_or(&styled), &c);
+        }
+        assert_eq!(text.to_original(0..text.folded().len()), 0..original.len());
+    }
+
+    #[cfg(all(feature = "fraktur", feature = "sans_serif"))]
+    #[test]
+    fn find_styled_occurrences() {
+        let haystack = "admin 𝖺𝖽𝗆𝗂𝗇 𝔞𝔡𝔪𝔦𝔫!";
+        let found: Vec<_> = find_ignore_font(haystack, "admin")
+            .map(|range| &haystack[range])
+            .collect();
+        assert_eq!(found, vec!["admin", "𝖺𝖽𝗆𝗂𝗇", "𝔞𝔡𝔪𝔦𝔫"]);
+        assert_eq!(find_ignore_font(haystack, "").count(), 0);
+        assert_eq!(find_ignore_font(haystack, "root").count(), 0);
+        assert_eq!(find_ignore_font("aaa", "aa").count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn offset_inside_char() {
+        FoldedText::new("é").to_original_offset(1);
+    }
+
+    #[cfg(feature = "bold")]
+    fn hash<T: Hash>(value: T) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn comparisons() {
+        assert!(eq_ignore_font("𝔞𝐛c", "abc"));
+        assert!(!eq_ignore_font("ab", "abc"));
+        assert_eq!(cmp_ignore_font("𝐚", "b"), Ordering::Less);
+        assert_eq!(cmp_ignore_font("𝐛", "a"), Ordering::Greater);
+        assert!(eq_ignore_font_and_case("𝐀İ", "aİ"));
+        assert_eq!(cmp_ignore_font_and_case("𝐀", "a"), Ordering::Equal);
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn insensitive_hashing() {
+        assert_eq!(
+            hash(FontInsensitive("𝐀𝐥𝐢𝐜𝐞")),
+            hash(FontInsensitive("Alice"))
+        );
+        assert_eq!(
+            hash(FontCaseInsensitive(String::from("𝐀𝐥𝐢𝐜𝐞"))),
+            hash(FontCaseInsensitive(String::from("ALICE")))
+        );
+        assert_ne!(
+            hash((FontInsensitive("ab"), FontInsensitive("c"))),
+            hash((FontInsensitive("a"), FontInsensitive("bc")))
+        );
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn insensitive_ordering() {
+        let mut names = vec![
+            FontInsensitive("𝐜"),
+            FontInsensitive("b"),
+            FontInsensitive("𝐚"),
+        ];
+        names.sort();
+        let names: Vec<_> = names.into_iter().map(FontInsensitive::into_inner).collect();
+        assert_eq!(names, vec!["𝐚", "b", "𝐜"]);
+    }
+}
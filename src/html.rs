@@ -0,0 +1,604 @@
+//! Conversion of simple HTML into styled Unicode text.
+//!
+//! Inline formatting is interpreted and blocks are separated by line breaks,
+//! which is enough to produce plain-text alternatives of emails and notifications.
+//! This is not a full HTML parser.
+//!
+//! # Examples
+//!
+//! ```
+//! use unicode_font::html;
+//! assert_eq!(html::to_styled("<b>Hi</b> &amp; <i>bye</i>"), "𝐇𝐢 & 𝑏𝑦𝑒");
+//! ```
+
+use crate::{StyledText, Variant};
+
+/// Converts simple HTML into styled Unicode text.
+///
+/// The following tags are interpreted, possibly nested.
+/// - `<b>` and `<strong>` as [`Variant::Bold`]
+/// - `<i>` and `<em>` as [`Variant::Italic`]
+/// - `<b>` and `<i>` nested as [`Variant::BoldItalic`]
+/// - `<code>` as [`Variant::Monospace`]
+/// - `<sup>` as [`Variant::Superscript`]
+/// - `<sub>` as [`Variant::Subscript`]
+///
+/// Other tags and comments are stripped, and the content of `<script>` and `<style>` is dropped.
+/// Character references (`&amp;`, `&eacute;`, `&#233;`, `&#xE9;`, ...) are decoded.
+///
+/// As in a browser, runs of whitespace collapse into a single space, except inside `<pre>`.
+/// `<br>` becomes a new line, paragraphs and headings are separated by an empty line,
+/// and other blocks, like `<div>`, `<li>` or `<tr>`, start on a new line.
+///
+/// Characters with no version in the requested variant are left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::html;
+/// assert_eq!(html::to_styled("<strong>x<em>y</em></strong><sup>2</sup>"), "𝐱𝒚²");
+/// assert_eq!(html::to_styled("<p>Run <code>ls</code></p>"), "Run 𝚕𝚜");
+/// ```
+pub fn to_styled(html: &str) -> String {
+    parse(html).to_unicode()
+}
+
+/// Parses simple HTML into [`StyledText`].
+///
+/// See [`to_styled`] for the supported subset of HTML.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{html, Span, Variant};
+/// let text = html::parse("<p>a <em>b</em></p>");
+/// assert_eq!(text.text(), "a b");
+/// assert_eq!(text.spans(), &[Span { range: 2..3, variant: Variant::Italic }]);
+/// ```
+pub fn parse(html: &str) -> StyledText {
+    let mut out = Output::default();
+    let mut style = Style::default();
+    let mut rest = html;
+    while let Some(position) = rest.find(['<', '&']) {
+        out.push(&rest[..position], style.variant());
+        rest = &rest[position..];
+        if rest.starts_with('&') {
+            let (decoded, len) = decode_reference(rest);
+            out.push_verbatim(&decoded, style.variant());
+            rest = &rest[len..];
+        } else if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else {
+            let Some(end) = tag_end(rest) else {
+                // Not a tag, it is just text
+                out.push("<", style.variant());
+                rest = &rest[1..];
+                continue;
+            };
+            let tag = Tag::parse(&rest[1..end]);
+            rest = &rest[end + 1..];
+            match tag.name.as_str() {
+                "script" | "style" if !tag.closing => {
+                    rest = skip_raw_text(rest, &tag.name);
+                }
+                "br" => out.line_breaks += 1,
+                "pre" => {
+                    out.line_breaks = out.line_breaks.max(1);
+                    if tag.closing {
+                        out.preformatted = out.preformatted.saturating_sub(1);
+                    } else if !tag.self_closing {
+                        out.preformatted += 1;
+                    }
+                }
+                "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    out.line_breaks = out.line_breaks.max(2)
+                }
+                "address" | "article" | "aside" | "blockquote" | "dd" | "div" | "dl" | "dt"
+                | "figure" | "footer" | "form" | "header" | "hr" | "li" | "main" | "nav" | "ol"
+                | "section" | "table" | "tr" | "ul" => out.line_breaks = out.line_breaks.max(1),
+                _ => style.update(&tag),
+            }
+        }
+    }
+    out.push(rest, style.variant());
+    out.text
+}
+
+/// Text being parsed, with the whitespace and line breaks waiting for the next text.
+///
+/// Whitespace and line breaks before the first text and after the last one are dropped.
+#[derive(Debug, Default)]
+struct Output {
+    text: StyledText,
+    /// Variant of the whitespace seen since the last text, if any.
+    space: Option<Option<Variant>>,
+    /// Number of line breaks to write before the next text.
+    line_breaks: usize,
+    /// Number of open `<pre>` tags, in which whitespace is kept.
+    preformatted: usize,
+}
+
+impl Output {
+    /// Pushes text from the document, collapsing whitespace outside of `<pre>`.
+    fn push(&mut self, text: &str, variant: Option<Variant>) {
+        if self.preformatted > 0 {
+            return self.push_verbatim(text, variant);
+        }
+        for (i, word) in text.split(is_whitespace).enumerate() {
+            if i > 0 && self.space.is_none() {
+                self.space = Some(variant);
+            }
+            if !word.is_empty() {
+                self.push_verbatim(word, variant);
+            }
+        }
+    }
+
+    /// Pushes text as is, after the pending whitespace or line breaks.
+    fn push_verbatim(&mut self, text: &str, variant: Option<Variant>) {
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.text().is_empty() {
+            if self.line_breaks > 0 {
+                self.text.push(&"\n".repeat(self.line_breaks), None);
+            } else if let Some(space_variant) = self.space {
+                self.text.push(" ", space_variant);
+            }
+        }
+        self.space = None;
+        self.line_breaks = 0;
+        self.text.push(text, variant);
+    }
+}
+
+/// Whitespace of HTML, which does not include the no-break space of `&nbsp;`.
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0C')
+}
+
+/// Currently open formatting tags.
+#[derive(Debug, Default)]
+struct Style {
+    bold: usize,
+    italic: usize,
+    code: usize,
+    superscript: usize,
+    subscript: usize,
+}
+
+impl Style {
+    fn update(&mut self, tag: &Tag) {
+        let counter = match tag.name.as_str() {
+            "b" | "strong" => &mut self.bold,
+            "i" | "em" => &mut self.italic,
+            "code" => &mut self.code,
+            "sup" => &mut self.superscript,
+            "sub" => &mut self.subscript,
+            _ => return,
+        };
+        if tag.closing {
+            *counter = counter.saturating_sub(1);
+        } else if !tag.self_closing {
+            *counter += 1;
+        }
+    }
+
+    fn variant(&self) -> Option<Variant> {
+        if self.superscript > 0 {
+            return Some(Variant::Superscript);
+        }
+        if self.subscript > 0 {
+            return Some(Variant::Subscript);
+        }
+        if self.code > 0 {
+            return Some(Variant::Monospace);
+        }
+        match (self.bold > 0, self.italic > 0) {
+            (true, true) => Some(Variant::BoldItalic),
+            (true, false) => Some(Variant::Bold),
+            (false, true) => Some(Variant::Italic),
+            (false, false) => None,
+        }
+    }
+}
+
+/// A start or end tag.
+#[derive(Debug)]
+struct Tag {
+    /// Lowercase name of the tag.
+    name: String,
+    closing: bool,
+    self_closing: bool,
+}
+
+impl Tag {
+    /// Parses the inside of `<...>`.
+    fn parse(s: &str) -> Self {
+        let (closing, s) = match s.strip_prefix('/') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let name = s
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        Tag {
+            name,
+            closing,
+            self_closing: s.trim_end().ends_with('/'),
+        }
+    }
+}
+
+/// Returns the position of the `>` closing the tag that starts `s`, if `s` starts with a tag.
+fn tag_end(s: &str) -> Option<usize> {
+    let first = s[1..].chars().next()?;
+    if !(first.is_ascii_alphabetic() || first == '/' || first == '!') {
+        return None;
+    }
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Skips the content of a raw text element, like `<script>`, up to and including its end tag.
+fn skip_raw_text<'a>(s: &'a str, name: &str) -> &'a str {
+    let end_tag = format!("</{name}");
+    match s.to_ascii_lowercase().find(&end_tag) {
+        Some(start) => s[start..].find('>').map_or("", |end| &s[start + end + 1..]),
+        None => "",
+    }
+}
+
+/// Decodes the character reference that starts `s`.
+///
+/// Returns the decoded text and the number of bytes consumed.
+/// Unknown references are kept as they are.
+fn decode_reference(s: &str) -> (String, usize) {
+    let not_a_reference = (String::from("&"), 1);
+    let Some(end) = s.find(';').filter(|&end| end <= 32) else {
+        return not_a_reference;
+    };
+    let name = &s[1..end];
+    let decoded = if let Some(hex) = name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+    } else if let Some(decimal) = name.strip_prefix('#') {
+        decimal.parse().ok().and_then(char::from_u32)
+    } else {
+        ENTITIES
+            .binary_search_by_key(&name, |(name, _)| name)
+            .ok()
+            .map(|i| ENTITIES[i].1)
+    };
+    match decoded {
+        Some(c) => (c.to_string(), end + 1),
+        None => not_a_reference,
+    }
+}
+
+/// Named character references of HTML 4, and `&apos;`, sorted by name.
+const ENTITIES: [(&str, char); 253] = [
+    ("AElig", 'Æ'),
+    ("Aacute", 'Á'),
+    ("Acirc", 'Â'),
+    ("Agrave", 'À'),
+    ("Alpha", 'Α'),
+    ("Aring", 'Å'),
+    ("Atilde", 'Ã'),
+    ("Auml", 'Ä'),
+    ("Beta", 'Β'),
+    ("Ccedil", 'Ç'),
+    ("Chi", 'Χ'),
+    ("Dagger", '‡'),
+    ("Delta", 'Δ'),
+    ("ETH", 'Ð'),
+    ("Eacute", 'É'),
+    ("Ecirc", 'Ê'),
+    ("Egrave", 'È'),
+    ("Epsilon", 'Ε'),
+    ("Eta", 'Η'),
+    ("Euml", 'Ë'),
+    ("Gamma", 'Γ'),
+    ("Iacute", 'Í'),
+    ("Icirc", 'Î'),
+    ("Igrave", 'Ì'),
+    ("Iota", 'Ι'),
+    ("Iuml", 'Ï'),
+    ("Kappa", 'Κ'),
+    ("Lambda", 'Λ'),
+    ("Mu", 'Μ'),
+    ("Ntilde", 'Ñ'),
+    ("Nu", 'Ν'),
+    ("OElig", 'Œ'),
+    ("Oacute", 'Ó'),
+    ("Ocirc", 'Ô'),
+    ("Ograve", 'Ò'),
+    ("Omega", 'Ω'),
+    ("Omicron", 'Ο'),
+    ("Oslash", 'Ø'),
+    ("Otilde", 'Õ'),
+    ("Ouml", 'Ö'),
+    ("Phi", 'Φ'),
+    ("Pi", 'Π'),
+    ("Prime", '″'),
+    ("Psi", 'Ψ'),
+    ("Rho", 'Ρ'),
+    ("Scaron", 'Š'),
+    ("Sigma", 'Σ'),
+    ("THORN", 'Þ'),
+    ("Tau", 'Τ'),
+    ("Theta", 'Θ'),
+    ("Uacute", 'Ú'),
+    ("Ucirc", 'Û'),
+    ("Ugrave", 'Ù'),
+    ("Upsilon", 'Υ'),
+    ("Uuml", 'Ü'),
+    ("Xi", 'Ξ'),
+    ("Yacute", 'Ý'),
+    ("Yuml", 'Ÿ'),
+    ("Zeta", 'Ζ'),
+    ("aacute", 'á'),
+    ("acirc", 'â'),
+    ("acute", '´'),
+    ("aelig", 'æ'),
+    ("agrave", 'à'),
+    ("alefsym", 'ℵ'),
+    ("alpha", 'α'),
+    ("amp", '&'),
+    ("and", '∧'),
+    ("ang", '∠'),
+    ("apos", '\''),
+    ("aring", 'å'),
+    ("asymp", '≈'),
+    ("atilde", 'ã'),
+    ("auml", 'ä'),
+    ("bdquo", '„'),
+    ("beta", 'β'),
+    ("brvbar", '¦'),
+    ("bull", '•'),
+    ("cap", '∩'),
+    ("ccedil", 'ç'),
+    ("cedil", '¸'),
+    ("cent", '¢'),
+    ("chi", 'χ'),
+    ("circ", 'ˆ'),
+    ("clubs", '♣'),
+    ("cong", '≅'),
+    ("copy", '©'),
+    ("crarr", '↵'),
+    ("cup", '∪'),
+    ("curren", '¤'),
+    ("dArr", '⇓'),
+    ("dagger", '†'),
+    ("darr", '↓'),
+    ("deg", '°'),
+    ("delta", 'δ'),
+    ("diams", '♦'),
+    ("divide", '÷'),
+    ("eacute", 'é'),
+    ("ecirc", 'ê'),
+    ("egrave", 'è'),
+    ("empty", '∅'),
+    ("emsp", '\u{2003}'),
+    ("ensp", '\u{2002}'),
+    ("epsilon", 'ε'),
+    ("equiv", '≡'),
+    ("eta", 'η'),
+    ("eth", 'ð'),
+    ("euml", 'ë'),
+    ("euro", '€'),
+    ("exist", '∃'),
+    ("fnof", 'ƒ'),
+    ("forall", '∀'),
+    ("frac12", '½'),
+    ("frac14", '¼'),
+    ("frac34", '¾'),
+    ("frasl", '⁄'),
+    ("gamma", 'γ'),
+    ("ge", '≥'),
+    ("gt", '>'),
+    ("hArr", '⇔'),
+    ("harr", '↔'),
+    ("hearts", '♥'),
+    ("hellip", '…'),
+    ("iacute", 'í'),
+    ("icirc", 'î'),
+    ("iexcl", '¡'),
+    ("igrave", 'ì'),
+    ("image", 'ℑ'),
+    ("infin", '∞'),
+    ("int", '∫'),
+    ("iota", 'ι'),
+    ("iquest", '¿'),
+    ("isin", '∈'),
+    ("iuml", 'ï'),
+    ("kappa", 'κ'),
+    ("lArr", '⇐'),
+    ("lambda", 'λ'),
+    ("lang", '〈'),
+    ("laquo", '«'),
+    ("larr", '←'),
+    ("lceil", '⌈'),
+    ("ldquo", '“'),
+    ("le", '≤'),
+    ("lfloor", '⌊'),
+    ("lowast", '∗'),
+    ("loz", '◊'),
+    ("lrm", '\u{200E}'),
+    ("lsaquo", '‹'),
+    ("lsquo", '‘'),
+    ("lt", '<'),
+    ("macr", '¯'),
+    ("mdash", '—'),
+    ("micro", 'µ'),
+    ("middot", '·'),
+    ("minus", '−'),
+    ("mu", 'μ'),
+    ("nabla", '∇'),
+    ("nbsp", '\u{A0}'),
+    ("ndash", '–'),
+    ("ne", '≠'),
+    ("ni", '∋'),
+    ("not", '¬'),
+    ("notin", '∉'),
+    ("nsub", '⊄'),
+    ("ntilde", 'ñ'),
+    ("nu", 'ν'),
+    ("oacute", 'ó'),
+    ("ocirc", 'ô'),
+    ("oelig", 'œ'),
+    ("ograve", 'ò'),
+    ("oline", '‾'),
+    ("omega", 'ω'),
+    ("omicron", 'ο'),
+    ("oplus", '⊕'),
+    ("or", '∨'),
+    ("ordf", 'ª'),
+    ("ordm", 'º'),
+    ("oslash", 'ø'),
+    ("otilde", 'õ'),
+    ("otimes", '⊗'),
+    ("ouml", 'ö'),
+    ("para", '¶'),
+    ("part", '∂'),
+    ("permil", '‰'),
+    ("perp", '⊥'),
+    ("phi", 'φ'),
+    ("pi", 'π'),
+    ("piv", 'ϖ'),
+    ("plusmn", '±'),
+    ("pound", '£'),
+    ("prime", '′'),
+    ("prod", '∏'),
+    ("prop", '∝'),
+    ("psi", 'ψ'),
+    ("quot", '"'),
+    ("rArr", '⇒'),
+    ("radic", '√'),
+    ("rang", '〉'),
+    ("raquo", '»'),
+    ("rarr", '→'),
+    ("rceil", '⌉'),
+    ("rdquo", '”'),
+    ("real", 'ℜ'),
+    ("reg", '®'),
+    ("rfloor", '⌋'),
+    ("rho", 'ρ'),
+    ("rlm", '\u{200F}'),
+    ("rsaquo", '›'),
+    ("rsquo", '’'),
+    ("sbquo", '‚'),
+    ("scaron", 'š'),
+    ("sdot", '⋅'),
+    ("sect", '§'),
+    ("shy", '\u{AD}'),
+    ("sigma", 'σ'),
+    ("sigmaf", 'ς'),
+    ("sim", '∼'),
+    ("spades", '♠'),
+    ("sub", '⊂'),
+    ("sube", '⊆'),
+    ("sum", '∑'),
+    ("sup", '⊃'),
+    ("sup1", '¹'),
+    ("sup2", '²'),
+    ("sup3", '³'),
+    ("supe", '⊇'),
+    ("szlig", 'ß'),
+    ("tau", 'τ'),
+    ("there4", '∴'),
+    ("theta", 'θ'),
+    ("thetasym", 'ϑ'),
+    ("thinsp", '\u{2009}'),
+    ("thorn", 'þ'),
+    ("tilde", '˜'),
+    ("times", '×'),
+    ("trade", '™'),
+    ("uArr", '⇑'),
+    ("uacute", 'ú'),
+    ("uarr", '↑'),
+    ("ucirc", 'û'),
+    ("ugrave", 'ù'),
+    ("uml", '¨'),
+    ("upsih", 'ϒ'),
+    ("upsilon", 'υ'),
+    ("uuml", 'ü'),
+    ("weierp", '℘'),
+    ("xi", 'ξ'),
+    ("yacute", 'ý'),
+    ("yen", '¥'),
+    ("yuml", 'ÿ'),
+    ("zeta", 'ζ'),
+    ("zwj", '\u{200D}'),
+    ("zwnj", '\u{200C}'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "bold_italic", feature = "italic"))]
+    #[test]
+    fn nested_formatting() {
+        assert_eq!(to_styled("<b>a<i>b</i>c</b>d"), "𝐚𝒃𝐜d");
+        assert_eq!(to_styled("<em><strong>a</strong></em>"), "𝒂");
+        assert_eq!(to_styled("<B>a</B>"), "𝐚");
+    }
+
+    #[cfg(all(feature = "extension", feature = "bold", feature = "subscript", feature = "superscript"))]
+    #[test]
+    fn subscripts_and_superscripts() {
+        assert_eq!(to_styled("<sub>2</sub><b>x<sup>2</sup></b>"), "₂𝐱²");
+    }
+
+    #[test]
+    fn other_tags_are_stripped() {
+        assert_eq!(to_styled(r#"<a href="x>y">link</a>"#), "link");
+        assert_eq!(to_styled("a<br/>b<br>c"), "a\nb\nc");
+        assert_eq!(to_styled("a<br><br>b<br>"), "a\n\nb");
+        assert_eq!(to_styled("a<!-- <b> -->b"), "ab");
+        assert_eq!(to_styled("a<script>let b = '<b>';</script>c"), "ac");
+        assert_eq!(to_styled("1 < 2"), "1 < 2");
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn references_are_decoded() {
+        assert_eq!(to_styled("&lt;&gt;&quot;&apos;&#65;&#x42;"), "<>\"'AB");
+        assert_eq!(
+            to_styled("caf&eacute; &mdash; it&rsquo;s&hellip; &copy;&nbsp;&Eacute;"),
+            "café — it’s… ©\u{A0}É"
+        );
+        assert_eq!(to_styled("<b>&#97;</b>"), "𝐚");
+        assert_eq!(to_styled("a & b &unknown; &#xFFFFFFF;"), "a & b &unknown; &#xFFFFFFF;");
+    }
+
+    #[test]
+    fn blocks_and_whitespace() {
+        assert_eq!(to_styled("<p>a</p><p>b</p>"), "a\n\nb");
+        assert_eq!(to_styled("<div>a</div><div>b</div>"), "a\nb");
+        assert_eq!(to_styled("<ul>\n  <li>a</li>\n  <li>b</li>\n</ul>"), "a\nb");
+        assert_eq!(
+            to_styled("<table><tr><td>a</td> <td>b</td></tr><tr><td>c</td></tr></table>"),
+            "a b\nc"
+        );
+        assert_eq!(to_styled("  a \n\t b  "), "a b");
+        assert_eq!(to_styled("a <pre>  b\n  c</pre> d"), "a\n  b\n  c\nd");
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn unclosed_tags() {
+        assert_eq!(to_styled("</b>a<b>b"), "a𝐛");
+        assert_eq!(to_styled("a<b"), "a<b");
+    }
+}
@@ -6,6 +6,11 @@
 //! Most of the conversions use mathematical characters.
 //! See [`Variant`] for the font variants included.
 //!
+//! Each variant has a cargo feature, named after its module, which is enabled by default.
+//! Variants whose feature is disabled have no characters.
+//! With `default-features = false`, the feature `all-variants`, or those of the variants needed,
+//! must be enabled, otherwise every table is empty.
+//!
 //! # Examples
 //!
 //! Transform a character to a few fonts.
@@ -27,6 +32,15 @@
 //! 
 //! assert_eq!(as_bold("abc"), Some(String::from("𝐚𝐛𝐜")));
 //! ```
+//!
+//! Build styled characters in `const` contexts.
+//! ```
+//! const LABEL: [char; 2] = match (unicode_font::as_bold('o'), unicode_font::as_bold('k')) {
+//!     (Some(o), Some(k)) => [o, k],
+//!     _ => panic!("no bold version"),
+//! };
+//! assert_eq!(LABEL, ['\u{1D428}', '\u{1D424}']); // 𝐨𝐤
+//! ```
 //
 // # Implementation
 //
@@ -36,14 +50,21 @@
 // Each convertion is a table for `char` to `char` conversion.
 // Some graphemes are longer than a `char`. Then, we use fixed sized arrays.
 
+// The code is indented with tabs, and so are examples in its documentation.
+#![allow(clippy::tabs_in_doc_comments)]
+
 macro_rules! modules { 
-	( $( $variant: ident ), *) => { 
+	( $( $variant: ident : $feature: literal ), *) => { 
 		$( 
 			paste::paste! { 
+				#[cfg(feature = $feature)]
 				pub use crate::$variant::[< try_as_ $variant >];  
+				#[cfg(feature = $feature)]
+				pub use crate::$variant::[< as_ $variant >];
 			}
 
 			#[doc= stringify!(Variant of Unicode symbols.)]
+			#[cfg(feature = $feature)]
 			pub mod $variant {
 				/// Mapping of plain characters to its variant.
 				///
@@ -70,6 +91,11 @@ macro_rules! modules {
 						// For a speed up, use the map directly
 						[< $variant:upper _MAP >].get(s)
 					}
+
+					/// Returns the variant version of a character if there is any, usable in `const` contexts.
+					pub const fn [< as_ $variant >](c: char) -> Option<char> {
+						crate::ranges::lookup(crate::ranges::[< $variant:upper >], c)
+					}
 				}
 
 				#[cfg(test)]
@@ -103,6 +129,15 @@ macro_rules! modules {
 					        assert_eq!(variant, double_variant);
 					    }
 					}
+
+					paste::paste! { 
+					    #[test]
+					    fn [< $variant _const_lookup_matches_map >]() {
+					    	for (c, styled) in [< $variant:upper _MAP >].entries() {
+					    		assert_eq!([< as_ $variant >](*c), Some(*styled));
+					    	}
+					    }
+					}
 				}
 
 			}
@@ -111,45 +146,86 @@ macro_rules! modules {
 }
 
 modules!(
-	arabic_mathematical, 
-	bold,
-	bold_italic,
-	bold_fraktur,
-	bold_script,
-	circled,
-	comma,
-	double_struck,
-	fraktur,
-	full_stop,
-	fullwidth,
-	italic,
-	looped,
-	monospace,
-	negative_circled,
-	negative_squared,
-	regional,
-	segmented,
-	other,
-	parenthesized,
-	sans_serif_bold_italic,
-	sans_serif_bold,
-	sans_serif_italic,
-	sans_serif,
-	script,
-	small_capital,
-	superscript,
-	subscript,
-	stretched,
-	squared,
-	tailed,
-	wide
+	arabic_mathematical: "arabic_mathematical",
+	arabic_mathematical_initial: "arabic_mathematical_initial",
+	bold: "bold",
+	bold_italic: "bold_italic",
+	bold_fraktur: "bold_fraktur",
+	bold_script: "bold_script",
+	circled: "circled",
+	comma: "comma",
+	double_struck: "double_struck",
+	double_struck_italic: "double_struck_italic",
+	fraktur: "fraktur",
+	full_stop: "full_stop",
+	fullwidth: "fullwidth",
+	italic: "italic",
+	looped: "looped",
+	monospace: "monospace",
+	negative_circled: "negative_circled",
+	negative_squared: "negative_squared",
+	regional: "regional",
+	segmented: "segmented",
+	other: "other",
+	parenthesized: "parenthesized",
+	sans_serif_bold_italic: "sans_serif_bold_italic",
+	sans_serif_bold: "sans_serif_bold",
+	sans_serif_italic: "sans_serif_italic",
+	sans_serif: "sans_serif",
+	mathematical_script: "mathematical_script",
+	script: "script",
+	small_capital: "small_capital",
+	superscript: "superscript",
+	subscript: "subscript",
+	stretched: "stretched",
+	squared: "squared",
+	tailed: "tailed",
+	wide: "wide"
 );
 
+pub mod accessibility;
+pub mod ansi;
+pub mod error;
+pub mod fold;
+pub mod html;
+#[cfg(feature = "regex")]
+pub mod regex;
+pub mod runs;
+pub mod spoof;
+pub mod stream;
+pub mod styled_text;
+pub mod transform;
 pub mod variant;
 
-pub use variant::Variant;
+pub use error::{ConversionError, ConversionReport};
+pub use runs::font_runs;
+pub use styled_text::{Span, StyledText};
+pub use transform::all_variants;
+pub use variant::{Variant, VariantSet};
 
-pub use plain::try_as_plain;
+pub use plain::{as_plain, try_as_plain};
+
+/// Unicode data the tables are generated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnicodeVersion {
+	/// Version of the Unicode standard, like `"15.1.0"`.
+	pub version: &'static str,
+	/// Lowercase hexadecimal SHA-256 of the `UnicodeData.txt` of the standard.
+	pub unicode_data_sha256: &'static str,
+	/// Lowercase hexadecimal SHA-256 of our extension, used with the feature `extension`.
+	pub extension_sha256: &'static str,
+}
+
+/// Unicode data the tables of this crate are generated from.
+///
+/// # Examples
+///
+/// ```
+/// let version = unicode_font::UNICODE_VERSION;
+/// assert!(version.version.starts_with("15."));
+/// assert_eq!(version.unicode_data_sha256.len(), 64);
+/// ```
+pub const UNICODE_VERSION: UnicodeVersion = include!("unicode_version");
 
 /// Plain variant of Unicode symbols.
 pub mod plain {
@@ -175,7 +251,6 @@ pub mod plain {
 	/// let fancy_zero = '\u{1D7D8}'; // 𝟘
 	/// assert_eq!(PLAIN_MAP.get(&fancy_zero).unwrap(), &'0');
 	/// ```
-
 	#[cfg(not(feature = "extension"))]
 	pub const PLAIN_MAP: phf::Map<char, char> = include!("plain");
 	#[cfg(feature = "extension")]
@@ -195,6 +270,18 @@ pub mod plain {
 	    PLAIN_MAP.get(s)
 	}
 
+	/// Returns the plain version of the character, usable in `const` contexts.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// const ZERO: Option<char> = unicode_font::as_plain('\u{1D7D8}'); // 𝟘
+	/// assert_eq!(ZERO, Some('0'));
+	/// ```
+	pub const fn as_plain(c: char) -> Option<char> {
+	    crate::ranges::lookup(crate::ranges::PLAIN, c)
+	}
+
 	#[cfg(test)]
 	mod tests {
 	    use super::*;
@@ -207,4 +294,55 @@ pub mod plain {
 	        assert_eq!(plain, double_plain);
 	    }
 	}
+}
+
+/// Runs of characters only supported with our extension, for each variant.
+///
+/// They explain failures when the extension is turned off, without including its maps.
+#[cfg(not(feature = "extension"))]
+mod extension_only {
+	include!("extension_only");
+
+	/// Binary search of a character in sorted runs.
+	pub(crate) fn contains(ranges: &[(char, char)], c: char) -> bool {
+		ranges
+			.binary_search_by(|(first, last)| {
+				if *last < c {
+					core::cmp::Ordering::Less
+				} else if c < *first {
+					core::cmp::Ordering::Greater
+				} else {
+					core::cmp::Ordering::Equal
+				}
+			})
+			.is_ok()
+	}
+}
+
+/// Runs of consecutive characters mapped to consecutive characters, for each variant.
+///
+/// They contain the same data as the maps, in a form that is faster to look up by value.
+mod ranges {
+	#[cfg(not(feature = "extension"))]
+	include!("ranges");
+	#[cfg(feature = "extension")]
+	include!("ranges.extension");
+
+	/// Binary search of a character in sorted runs, usable in `const` contexts.
+	pub(crate) const fn lookup(ranges: &[(char, char, char)], c: char) -> Option<char> {
+		let mut low = 0;
+		let mut high = ranges.len();
+		while low < high {
+			let middle = low + (high - low) / 2;
+			let (first, last, target) = ranges[middle];
+			if c < first {
+				high = middle;
+			} else if last < c {
+				low = middle + 1;
+			} else {
+				return char::from_u32(target as u32 + (c as u32 - first as u32));
+			}
+		}
+		None
+	}
 }
\ No newline at end of file
@@ -1,3 +1,248 @@
-fn main() {
-	unimplemented!();
-}
\ No newline at end of file
+//! Command-line tool converting text between fonts.
+//!
+//! Run `unicode_font --help` for usage.
+
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::process::ExitCode;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use unicode_font::Variant;
+
+mod cli {
+    pub mod chart;
+    pub mod convert;
+    pub mod inspect;
+    pub mod rewrite;
+    pub mod search;
+    pub mod stats;
+}
+
+const USAGE: &str = "\
+Convert unicode characters between fonts.
+
+Usage:
+    unicode_font convert [OPTIONS] [TEXT]...
+    unicode_font inspect [--json] [TEXT]...
+    unicode_font fold [--check] [--diff] [--variants <NAMES>] FILE...
+    unicode_font style --variant <NAME> [--check] [--diff] FILE...
+    unicode_font search [--color <WHEN>] PATTERN [PATH]...
+    unicode_font chart [--format <FORMAT>] [--variants <NAMES>] [--range <RANGES>]
+    unicode_font stats [--json] [--top <N>] [FILE]...
+    unicode_font --list-variants
+
+Text is read from standard input when no TEXT is given.
+
+Commands:
+    convert    Convert text to a variant
+    inspect    Describe every character of a text
+    fold       Rewrite files replacing styled characters by their plain version
+    style      Rewrite files converting them to a variant
+    search     Find a pattern in files or standard input, ignoring fonts
+    chart      Print the version of plain characters in every variant
+    stats      Summarize the use of styles in messages, one per line
+
+Options of convert:
+    -v, --variant <NAME>    Variant to convert to, like `bold` or `double_struck`
+    -p, --to-plain          Convert to plain, same as `--variant plain`
+        --policy <POLICY>   What to do with characters that can not be converted:
+                            keep (default), replace, drop or error
+
+Options of inspect:
+        --json              Print a JSON array instead of a table
+
+Options of fold and style:
+        --check             Only report the characters that would be rewritten
+        --diff              Print a unified diff instead of writing the files
+        --variants <NAMES>  Comma-separated variants to fold, by default the
+                            mathematical ones, like `bold` or `fraktur`
+    -v, --variant <NAME>    Variant to style the files with
+
+Options of search:
+        --color <WHEN>      Highlight matches: auto (default), always or never
+
+Options of chart:
+        --format <FORMAT>   markdown (default), html or csv
+        --variants <NAMES>  Comma-separated variants to show, all by default
+        --range <RANGES>    Comma-separated plain characters to show, like `A-Z,0-9`
+
+Options of stats:
+        --json              Print a JSON object instead of tables
+        --top <N>           Number of most common styled words listed, 10 by default
+
+Options:
+        --list-variants     List the variants, with a sample of each
+    -h, --help              Print this help
+
+With `--policy error`, the position of every character that can not be converted
+is reported and the exit status is 1.
+With `--check` or `--diff`, files are not modified and the exit status is 1 if any would be.";
+
+/// Exit status of invalid arguments or input.
+const USAGE_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match Command::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+    match command.run() {
+        Ok(status) => status,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::from(USAGE_ERROR)
+        }
+    }
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Help,
+    ListVariants,
+    Convert(cli::convert::Args),
+    Inspect(cli::inspect::Args),
+    Rewrite(cli::rewrite::Args),
+    Search(cli::search::Args),
+    Chart(cli::chart::Args),
+    Stats(cli::stats::Args),
+}
+
+impl Command {
+    /// Parses the arguments, without the name of the program.
+    ///
+    /// Global options are only recognised in the first position, and `-h` or
+    /// `--help` right after a command, so that they can be given as text or
+    /// file names, like in `convert -p -- --help`.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((subcommand, args)) = args.split_first() else {
+            return Ok(Command::Help);
+        };
+        if matches!(args.first().map(String::as_str), Some("-h" | "--help")) {
+            return Ok(Command::Help);
+        }
+        match subcommand.as_str() {
+            "-h" | "--help" => Ok(Command::Help),
+            "--list-variants" => Ok(Command::ListVariants),
+            "convert" => cli::convert::Args::parse(args).map(Command::Convert),
+            "inspect" => cli::inspect::Args::parse(args).map(Command::Inspect),
+            "fold" => cli::rewrite::Args::parse_fold(args).map(Command::Rewrite),
+            "style" => cli::rewrite::Args::parse_style(args).map(Command::Rewrite),
+            "search" => cli::search::Args::parse(args).map(Command::Search),
+            "chart" => cli::chart::Args::parse(args).map(Command::Chart),
+            "stats" => cli::stats::Args::parse(args).map(Command::Stats),
+            _ => Err(format!("unknown command {subcommand:?}")),
+        }
+    }
+
+    /// Runs the command, returning the exit status.
+    fn run(self) -> Result<ExitCode, String> {
+        match self {
+            Command::Help => write_stdout(&format!("{USAGE}\n"))?,
+            Command::ListVariants => {
+                let mut out = String::new();
+                for variant in Variant::iter() {
+                    writeln!(
+                        out,
+                        "{:<28} {}",
+                        snake_case(variant),
+                        cli::convert::sample(variant)
+                    )
+                    .expect("writing to a string does not fail");
+                }
+                write_stdout(&out)?;
+            }
+            Command::Convert(args) => return cli::convert::run(args),
+            Command::Inspect(args) => return cli::inspect::run(args),
+            Command::Rewrite(args) => return cli::rewrite::run(args),
+            Command::Search(args) => return cli::search::run(args),
+            Command::Chart(args) => return cli::chart::run(args),
+            Command::Stats(args) => return cli::stats::run(args),
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Parses a variant name, ignoring case, `_` and `-`.
+fn parse_variant(name: &str) -> Result<Variant, String> {
+    let compact: String = name.chars().filter(|c| !matches!(c, '_' | '-')).collect();
+    Variant::from_str(&compact)
+        .map_err(|_| format!("unknown variant {name:?}, see --list-variants"))
+}
+
+/// Name of the variant in snake case, as accepted by `--variant`.
+fn snake_case(variant: Variant) -> String {
+    let mut name = String::new();
+    for c in variant.to_string().chars() {
+        if c.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// Text given as arguments, or standard input if there are none.
+///
+/// Arguments are joined by spaces and end with a newline.
+fn input_text(words: Option<&str>) -> Result<String, String> {
+    if let Some(words) = words {
+        return Ok(format!("{words}\n"));
+    }
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("failed reading standard input: {e}"))?;
+    Ok(input)
+}
+
+/// Writes to standard output, stopping quietly if the reader went away, as in `| head`.
+fn write_stdout(s: &str) -> Result<(), String> {
+    match std::io::stdout().lock().write_all(s.as_bytes()) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+            Err(format!("failed writing output: {e}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            Command::parse(&args("--list-variants")),
+            Ok(Command::ListVariants)
+        );
+        assert_eq!(Command::parse(&args("inspect -h")), Ok(Command::Help));
+        assert_eq!(Command::parse(&args("")), Ok(Command::Help));
+        assert_eq!(
+            Command::parse(&args("convert -p -- --help --list-variants")),
+            Ok(Command::Convert(cli::convert::Args {
+                variant: Variant::Plain,
+                policy: cli::convert::Policy::Keep,
+                text: Some("--help --list-variants".to_string()),
+            }))
+        );
+        assert!(Command::parse(&args("convert -p --list-variants")).is_err());
+        assert!(Command::parse(&args("frobnicate")).is_err());
+    }
+
+    #[test]
+    fn variant_names_round_trip() {
+        for variant in Variant::iter() {
+            assert_eq!(parse_variant(&snake_case(variant)), Ok(variant));
+        }
+        assert_eq!(parse_variant("Double-Struck"), Ok(Variant::DoubleStruck));
+    }
+}
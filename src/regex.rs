@@ -0,0 +1,256 @@
+//! Regular expressions that match any font variant.
+//!
+//! Literal characters of a plain pattern are expanded into character classes
+//! of all their styled versions, so the pattern runs unchanged inside the [`regex`] engine.
+//!
+//! Requires the `regex` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! let re = unicode_font::regex::build_any_font("free money").unwrap();
+//! assert!(re.is_match("𝔣𝔯𝔢𝔢 𝔪𝔬𝔫𝔢𝔶"));
+//! assert!(re.is_match("𝐟𝐫𝐞𝐞 money"));
+//! ```
+
+use crate::{Variant, VariantSet};
+use ::regex::{Error, Regex};
+use regex_syntax::ast::{self, Ast};
+use regex_syntax::hir::{
+    translate::Translator, Capture, Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind,
+    Repetition,
+};
+
+/// Name of the groups wrapping negated classes while expanding.
+///
+/// Classes are negated by the translation to [`Hir`], so they are translated un-negated
+/// in a group of this name, and negated once the styled versions of their members are added.
+const NEGATED: &str = "unicode_font_negated";
+
+/// Builds a regular expression matching the pattern in any font variant.
+///
+/// # Errors
+///
+/// If the pattern is not a valid regular expression.
+pub fn build_any_font(pattern: &str) -> Result<Regex, Error> {
+    build(pattern, &VariantSet::all())
+}
+
+/// Builds a regular expression matching the pattern in plain or in any of the given variants.
+///
+/// # Errors
+///
+/// If the pattern is not a valid regular expression.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{Variant, VariantSet};
+/// let variants: VariantSet = [Variant::Bold, Variant::Italic].into_iter().collect();
+/// let re = unicode_font::regex::build(r"^\d+ [a-z]+$", &variants).unwrap();
+/// assert!(re.is_match("𝟏2 𝑎𝐛c"));
+/// assert!(!re.is_match("12 𝔞𝔟𝔠"));
+/// ```
+pub fn build(pattern: &str, variants: &VariantSet) -> Result<Regex, Error> {
+    Regex::new(&expand(pattern, variants)?)
+}
+
+/// Expands a pattern so that it matches plain text or any of the given variants.
+///
+/// The result is a pattern that can be compiled by the [`regex`] crate.
+///
+/// # Errors
+///
+/// If the pattern is not a valid regular expression.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::Variant;
+/// let pattern = unicode_font::regex::expand("a", &Variant::Bold.into()).unwrap();
+/// assert_eq!(pattern, "[a𝐚]");
+/// ```
+pub fn expand(pattern: &str, variants: &VariantSet) -> Result<String, Error> {
+    let mut ast = ast::parse::Parser::new()
+        .parse(pattern)
+        .map_err(|e| Error::Syntax(e.to_string()))?;
+    mark_negated(&mut ast);
+    let hir = Translator::new()
+        .translate(pattern, &ast)
+        .map_err(|e| Error::Syntax(e.to_string()))?;
+    Ok(expand_hir(&hir, variants).to_string())
+}
+
+/// Replaces negated bracketed classes by their un-negated version in a group named [`NEGATED`].
+fn mark_negated(ast: &mut Ast) {
+    match ast {
+        Ast::ClassBracketed(class) if class.negated => {
+            class.negated = false;
+            let span = class.span;
+            let class = core::mem::replace(ast, Ast::empty(span));
+            *ast = Ast::group(ast::Group {
+                span,
+                kind: ast::GroupKind::CaptureName {
+                    starts_with_p: false,
+                    name: ast::CaptureName {
+                        span,
+                        name: NEGATED.to_string(),
+                        index: 0,
+                    },
+                },
+                ast: Box::new(class),
+            });
+        }
+        Ast::Repetition(repetition) => mark_negated(&mut repetition.ast),
+        Ast::Group(group) => mark_negated(&mut group.ast),
+        Ast::Alternation(alternation) => alternation.asts.iter_mut().for_each(mark_negated),
+        Ast::Concat(concat) => concat.asts.iter_mut().for_each(mark_negated),
+        _ => {}
+    }
+}
+
+fn expand_hir(hir: &Hir, variants: &VariantSet) -> Hir {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) | HirKind::Class(Class::Bytes(_)) => hir.clone(),
+        HirKind::Literal(literal) => match core::str::from_utf8(&literal.0) {
+            Ok(s) => Hir::concat(
+                s.chars()
+                    .map(|c| {
+                        let class = ClassUnicode::new([ClassUnicodeRange::new(c, c)]);
+                        Hir::class(Class::Unicode(expand_class(&class, variants)))
+                    })
+                    .collect(),
+            ),
+            Err(_) => hir.clone(),
+        },
+        HirKind::Class(Class::Unicode(class)) => {
+            Hir::class(Class::Unicode(expand_class(class, variants)))
+        }
+        HirKind::Repetition(repetition) => Hir::repetition(Repetition {
+            sub: Box::new(expand_hir(&repetition.sub, variants)),
+            ..repetition.clone()
+        }),
+        HirKind::Capture(capture) if capture.name.as_deref() == Some(NEGATED) => {
+            expand_negated(&capture.sub, variants)
+        }
+        HirKind::Capture(capture) => Hir::capture(Capture {
+            sub: Box::new(expand_hir(&capture.sub, variants)),
+            ..capture.clone()
+        }),
+        HirKind::Concat(subs) => {
+            Hir::concat(subs.iter().map(|sub| expand_hir(sub, variants)).collect())
+        }
+        HirKind::Alternation(subs) => {
+            Hir::alternation(subs.iter().map(|sub| expand_hir(sub, variants)).collect())
+        }
+    }
+}
+
+/// Negates an un-negated class after adding the styled versions of its characters,
+/// so that these are excluded too.
+fn expand_negated(hir: &Hir, variants: &VariantSet) -> Hir {
+    let class = match hir.kind() {
+        HirKind::Class(Class::Unicode(class)) => class.clone(),
+        HirKind::Class(Class::Bytes(class)) => {
+            let mut class = class.clone();
+            class.negate();
+            return Hir::class(Class::Bytes(class));
+        }
+        // Classes of a single character are translated to literals.
+        HirKind::Literal(literal) => match core::str::from_utf8(&literal.0) {
+            Ok(s) => ClassUnicode::new(s.chars().map(|c| ClassUnicodeRange::new(c, c))),
+            Err(_) => return hir.clone(),
+        },
+        _ => return hir.clone(),
+    };
+    let mut expanded = expand_class(&class, variants);
+    expanded.negate();
+    Hir::class(Class::Unicode(expanded))
+}
+
+/// Adds the styled versions of the characters in the class.
+fn expand_class(class: &ClassUnicode, variants: &VariantSet) -> ClassUnicode {
+    let contains = |c: &char| {
+        class
+            .ranges()
+            .binary_search_by(|range| {
+                if range.end() < *c {
+                    core::cmp::Ordering::Less
+                } else if range.start() > *c {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    };
+    let mut expanded = class.clone();
+    for variant in variants.iter().filter(|variant| variant != &Variant::Plain) {
+        let styled = variant
+            .map()
+            .entries()
+            .filter(|(plain, _)| contains(plain))
+            .map(|(_, styled)| ClassUnicodeRange::new(*styled, *styled));
+        expanded.union(&ClassUnicode::new(styled));
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(
+        feature = "extension",
+        feature = "bold",
+        feature = "circled",
+        feature = "fraktur",
+        feature = "italic",
+        feature = "sans_serif"
+    ))]
+    #[test]
+    fn literals_match_styled_versions() {
+        let re = build_any_font("admin").unwrap();
+        for s in ["admin", "𝖺𝖽𝗆𝗂𝗇", "𝔞𝔡𝔪𝔦𝔫", "a𝐝𝑚ⓘ𝗇"] {
+            assert!(re.is_match(s), "{s}");
+        }
+        assert!(!re.is_match("root"));
+    }
+
+    #[cfg(feature = "fraktur")]
+    #[test]
+    fn restricted_variants() {
+        let re = build("ab", &Variant::Fraktur.into()).unwrap();
+        assert!(re.is_match("a𝔟"));
+        assert!(!re.is_match("a𝐛"));
+        let re = build("ab", &VariantSet::new()).unwrap();
+        assert!(!re.is_match("a𝔟"));
+    }
+
+    #[cfg(all(feature = "bold", feature = "double_struck"))]
+    #[test]
+    fn structure_is_preserved() {
+        let re = build_any_font(r"(?i)^(?P<word>hi|yo)\s+x{2}$").unwrap();
+        let captures = re.captures("𝐇𝐈  𝕩𝕩").unwrap();
+        assert_eq!(&captures["word"], "𝐇𝐈");
+        assert!(!re.is_match("𝐇𝐈 𝕩"));
+        assert!(re.is_match("yO xx"));
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn negated_classes_exclude_styled_versions() {
+        let re = build("^[^a]$", &Variant::Bold.into()).unwrap();
+        assert!(!re.is_match("a"));
+        assert!(!re.is_match("𝐚"));
+        assert!(re.is_match("b") && re.is_match("𝐛"));
+        let re = build("^[^a-c]x$", &Variant::Bold.into()).unwrap();
+        assert!(!re.is_match("𝐛x"));
+        assert!(re.is_match("𝐝𝐱"));
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        assert!(matches!(build_any_font("(a"), Err(Error::Syntax(_))));
+    }
+}
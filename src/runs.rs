@@ -0,0 +1,167 @@
+//! Segmentation of text into runs of the same font variant.
+
+use crate::Variant;
+use core::ops::Range;
+
+/// Consecutive characters of the same font variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Run {
+    /// Byte range in the original text.
+    pub range: Range<usize>,
+    /// Detected variant, or `None` for neutral characters.
+    ///
+    /// Neutral characters are those absent from every map, like spaces.
+    pub variant: Option<Variant>,
+    /// Plain version of the run.
+    pub plain: String,
+}
+
+/// Splits text into runs of characters with the same detected variant.
+///
+/// By default, neutral characters surrounded by characters of the same variant
+/// are joined into that run, so that `"𝔣𝔯𝔢𝔢 𝔪𝔬𝔫𝔢𝔶"` is a single run.
+/// See [`FontRuns::join_neutral`] to change this behaviour.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{font_runs, Variant};
+/// let runs: Vec<_> = font_runs("Buy 𝔣𝔯𝔢𝔢 𝔪𝔬𝔫𝔢𝔶!").collect();
+/// assert_eq!(runs.len(), 4);
+/// assert_eq!(runs[2].variant, Some(Variant::Fraktur));
+/// assert_eq!(runs[2].plain, "free money");
+/// assert_eq!(runs[3].variant, Some(Variant::Plain)); // ! has a fullwidth version
+/// ```
+pub fn font_runs(s: &str) -> FontRuns<'_> {
+    FontRuns {
+        s,
+        position: 0,
+        join_neutral: true,
+    }
+}
+
+/// Iterator over the [`Run`]s of a text.
+///
+/// This struct is created by [`font_runs`].
+#[derive(Debug, Clone)]
+pub struct FontRuns<'a> {
+    s: &'a str,
+    position: usize,
+    join_neutral: bool,
+}
+
+impl FontRuns<'_> {
+    /// Sets whether neutral characters surrounded by the same variant are joined into its run.
+    ///
+    /// Neutral characters at the border of two different variants always form their own run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::font_runs;
+    /// assert_eq!(font_runs("𝐚 𝐛").count(), 1);
+    /// assert_eq!(font_runs("𝐚 𝐛").join_neutral(false).count(), 3);
+    /// ```
+    pub fn join_neutral(mut self, join: bool) -> Self {
+        self.join_neutral = join;
+        self
+    }
+}
+
+impl Iterator for FontRuns<'_> {
+    type Item = Run;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.s[self.position..];
+        let first = rest.chars().next()?;
+        let variant = Variant::detect(&first);
+        let mut plain = String::new();
+        let mut end = 0;
+        let mut chars = rest.char_indices().peekable();
+        while let Some(&(i, c)) = chars.peek() {
+            let current = Variant::detect(&c);
+            if current == variant {
+                plain.push(Variant::Plain.convert(c).unwrap_or(c));
+                end = i + c.len_utf8();
+                chars.next();
+                continue;
+            }
+            if !(self.join_neutral && variant.is_some() && current.is_none()) {
+                break;
+            }
+            // Join the neutral characters only if the same variant follows them
+            let neutral_end = rest[i..]
+                .char_indices()
+                .find(|(_, c)| Variant::detect(c).is_some())
+                .map(|(j, c)| (i + j, c));
+            match neutral_end {
+                Some((j, c)) if Variant::detect(&c) == variant => {
+                    plain.push_str(&rest[i..j]);
+                    end = j;
+                    while chars.next_if(|&(k, _)| k < j).is_some() {}
+                }
+                _ => break,
+            }
+        }
+        let range = self.position..self.position + end;
+        self.position = range.end;
+        Some(Run {
+            range,
+            variant,
+            plain,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bold")]
+    fn summary(runs: FontRuns) -> Vec<(Range<usize>, Option<Variant>, String)> {
+        runs.map(|run| (run.range, run.variant, run.plain))
+            .collect()
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn neutral_joining() {
+        assert_eq!(
+            summary(font_runs("𝐚, 𝐛 c")),
+            vec![
+                (0..10, Some(Variant::Bold), String::from("a, b")),
+                (10..11, None, String::from(" ")),
+                (11..12, Some(Variant::Plain), String::from("c")),
+            ]
+        );
+        assert_eq!(
+            summary(font_runs(" 𝐚 ").join_neutral(true)),
+            vec![
+                (0..1, None, String::from(" ")),
+                (1..5, Some(Variant::Bold), String::from("a")),
+                (5..6, None, String::from(" ")),
+            ]
+        );
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn without_joining() {
+        let variants: Vec<_> = font_runs("ab 𝐚𝐛 ")
+            .join_neutral(false)
+            .map(|run| run.variant)
+            .collect();
+        assert_eq!(
+            variants,
+            vec![Some(Variant::Plain), None, Some(Variant::Bold), None]
+        );
+    }
+
+    #[test]
+    fn runs_cover_text() {
+        let s = "Hi 𝔣𝔯𝔢𝔢, ℎ ⓐⓑ 𝟙!";
+        let covered: String = font_runs(s).map(|run| &s[run.range]).collect();
+        assert_eq!(covered, s);
+        assert_eq!(font_runs("").next(), None);
+    }
+}
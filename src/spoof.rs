@@ -0,0 +1,144 @@
+//! Detection of identifiers spoofed with font variants.
+//!
+//! Identifiers like `"pay𝗉al"` pass naive checks because only one letter is styled.
+//! In the spirit of [Unicode Technical Standard #39](https://www.unicode.org/reports/tr39/),
+//! this module computes a font-folded skeleton of identifiers and reports mixed font variants.
+//! It complements, and does not replace, a confusables check.
+//!
+//! # Examples
+//!
+//! ```
+//! use unicode_font::spoof;
+//! let report = spoof::check_identifier("pay𝗉al");
+//! assert!(report.is_mixed());
+//! assert_eq!(report.skeleton, "paypal");
+//! assert_eq!(report.collisions(&["paypal", "ebay"]), vec!["paypal"]);
+//! ```
+
+use crate::Variant;
+
+/// Result of checking an identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentifierReport {
+    /// Identifier with every character converted to plain.
+    pub skeleton: String,
+    /// Variants found in the identifier, in order of first appearance.
+    ///
+    /// Neutral characters, those absent from every map, are not taken into account.
+    pub variants: Vec<Variant>,
+    /// Byte index and character of every styled character, i.e. not plain nor neutral.
+    pub styled: Vec<(usize, char)>,
+}
+
+impl IdentifierReport {
+    /// Returns `true` if the identifier mixes font variants, plain included.
+    pub fn is_mixed(&self) -> bool {
+        self.variants.len() > 1
+    }
+
+    /// Returns `true` if the identifier has styled characters.
+    pub fn is_styled(&self) -> bool {
+        !self.styled.is_empty()
+    }
+
+    /// Returns the protected names whose skeleton is the same as the skeleton of the identifier.
+    ///
+    /// Comparison is exact, so case-insensitive identifiers should be lowercased beforehand.
+    pub fn collisions<'a>(&self, protected: &[&'a str]) -> Vec<&'a str> {
+        protected
+            .iter()
+            .filter(|name| skeleton(name) == self.skeleton)
+            .copied()
+            .collect()
+    }
+}
+
+/// Checks an identifier for font variants.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{spoof, Variant};
+/// let report = spoof::check_identifier("𝐚𝐝𝐦𝐢𝐧_𝟏");
+/// assert!(!report.is_mixed());
+/// assert_eq!(report.variants, vec![Variant::Bold]);
+/// assert_eq!(report.styled.len(), 6);
+/// assert!(spoof::check_identifier("𝐚𝐝𝐦𝐢𝐧_1").is_mixed());
+/// ```
+pub fn check_identifier(id: &str) -> IdentifierReport {
+    let mut variants = Vec::new();
+    let mut styled = Vec::new();
+    for (i, c) in id.char_indices() {
+        let Some(variant) = Variant::detect(&c) else {
+            continue;
+        };
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+        if variant != Variant::Plain {
+            styled.push((i, c));
+        }
+    }
+    IdentifierReport {
+        skeleton: skeleton(id),
+        variants,
+        styled,
+    }
+}
+
+/// Converts every character of an identifier to plain.
+///
+/// Two identifiers with the same skeleton only differ in fonts.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::spoof;
+/// assert_eq!(spoof::skeleton("𝔞𝔡𝔪𝔦𝔫"), spoof::skeleton("admin"));
+/// ```
+pub fn skeleton(id: &str) -> String {
+    id.chars()
+        .map(|c| Variant::Plain.convert(c).unwrap_or(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sans_serif")]
+    #[test]
+    fn mixed_identifiers() {
+        let report = check_identifier("pay𝗉al");
+        assert_eq!(report.variants, vec![Variant::Plain, Variant::SansSerif]);
+        assert_eq!(report.styled, vec![(3, '𝗉')]);
+        assert!(report.is_mixed());
+
+        assert!(!check_identifier("paypal").is_mixed());
+        assert!(!check_identifier("paypal").is_styled());
+        assert!(!check_identifier("𝗉𝖺𝗒").is_mixed());
+    }
+
+    #[test]
+    fn plain_identifiers_are_their_own_skeleton() {
+        let report = check_identifier("user_name-1");
+        assert!(report.styled.is_empty());
+        assert_eq!(report.skeleton, "user_name-1");
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn neutral_characters_are_ignored() {
+        let report = check_identifier("a-b c");
+        assert_eq!(report.variants, vec![Variant::Plain]);
+        assert_eq!(report.skeleton, "a-b c");
+    }
+
+    #[cfg(all(feature = "bold", feature = "bold_italic"))]
+    #[test]
+    fn collisions_with_styled_protected_names() {
+        let report = check_identifier("𝒂𝒅𝒎𝒊𝒏");
+        assert_eq!(report.collisions(&["𝐚𝐝𝐦𝐢𝐧", "root"]), vec!["𝐚𝐝𝐦𝐢𝐧"]);
+        assert!(report.collisions(&["Admin"]).is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+//! Conversion of streams of text.
+//!
+//! [`StyledWriter`] and [`StyledReader`] convert UTF-8 bytes on the fly,
+//! so large texts do not need to be loaded in memory.
+//! [`StyledFmtWriter`] does the same for [`fmt::Write`], which is what [`write!`] uses.
+//!
+//! Characters are converted to the variant from any variant, as in [`all_variants`](crate::all_variants).
+//! Characters with no version in the variant are kept unchanged.
+//! Use [`Variant::Plain`] to convert to plain.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::Write;
+//! use unicode_font::{stream::StyledWriter, Variant};
+//! let mut writer = StyledWriter::new(Vec::new(), Variant::Bold);
+//! write!(writer, "Hello {}", 42).unwrap();
+//! let bytes = writer.finish().unwrap();
+//! assert_eq!(String::from_utf8(bytes).unwrap(), "𝐇𝐞𝐥𝐥𝐨 𝟒𝟐");
+//! ```
+
+use crate::Variant;
+use core::fmt;
+use std::io;
+
+/// Invalid UTF-8 found in a stream.
+///
+/// It is returned inside an [`io::Error`] of kind [`io::ErrorKind::InvalidData`].
+/// Use [`io::Error::get_ref`] and [`downcast_ref`](std::error::Error::downcast_ref) to get it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidUtf8 {
+    /// Byte position of the invalid sequence in the stream.
+    pub position: u64,
+}
+
+impl fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid UTF-8 sequence at byte {}", self.position)
+    }
+}
+
+impl std::error::Error for InvalidUtf8 {}
+
+impl From<InvalidUtf8> for io::Error {
+    fn from(error: InvalidUtf8) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+/// Decodes UTF-8 incrementally, keeping sequences split between buffers.
+#[derive(Debug, Clone)]
+struct Decoder {
+    variant: Variant,
+    /// Start of a sequence whose end is in the next buffer.
+    partial: Vec<u8>,
+    /// Number of bytes decoded or skipped so far.
+    position: u64,
+}
+
+impl Decoder {
+    fn new(variant: Variant) -> Self {
+        Decoder {
+            variant,
+            partial: Vec::with_capacity(4),
+            position: 0,
+        }
+    }
+
+    fn push_converted(&mut self, s: &str, out: &mut String) {
+        out.extend(s.chars().map(|c| convert(c, self.variant)));
+        self.position += s.len() as u64;
+    }
+
+    /// Converts the longest valid prefix of the bytes, returning the number of bytes consumed.
+    ///
+    /// An incomplete sequence at the end is consumed and kept until the next call.
+    /// If the bytes start with an invalid sequence, returns the error and the number of
+    /// bytes of the sequence in `bytes`.
+    fn decode(&mut self, bytes: &[u8], out: &mut String) -> Result<usize, (InvalidUtf8, usize)> {
+        let mut consumed = 0;
+        if let Some(first) = self.partial.first() {
+            let previous = self.partial.len();
+            let width = sequence_width(*first);
+            consumed = (width - previous).min(bytes.len());
+            self.partial.extend_from_slice(&bytes[..consumed]);
+            match core::str::from_utf8(&self.partial) {
+                Ok(s) => {
+                    out.extend(s.chars().map(|c| convert(c, self.variant)));
+                    self.position += width as u64;
+                    self.partial.clear();
+                }
+                Err(error) => match error.error_len() {
+                    None => return Ok(consumed),
+                    Some(len) => {
+                        let invalid = InvalidUtf8 {
+                            position: self.position,
+                        };
+                        self.position += len as u64;
+                        self.partial.clear();
+                        return Err((invalid, len.saturating_sub(previous)));
+                    }
+                },
+            }
+        }
+        let rest = &bytes[consumed..];
+        match core::str::from_utf8(rest) {
+            Ok(s) => {
+                self.push_converted(s, out);
+                Ok(bytes.len())
+            }
+            Err(error) => {
+                let valid = error.valid_up_to();
+                // The prefix is valid, so this can not fail
+                let s = core::str::from_utf8(&rest[..valid]).unwrap_or_default();
+                self.push_converted(s, out);
+                consumed += valid;
+                match error.error_len() {
+                    None => {
+                        self.partial.extend_from_slice(&rest[valid..]);
+                        Ok(bytes.len())
+                    }
+                    Some(len) if consumed == 0 => {
+                        let invalid = InvalidUtf8 {
+                            position: self.position,
+                        };
+                        self.position += len as u64;
+                        Err((invalid, len))
+                    }
+                    Some(_) => Ok(consumed),
+                }
+            }
+        }
+    }
+
+    /// Checks that the stream did not end in the middle of a sequence.
+    fn finish(&mut self) -> Result<(), InvalidUtf8> {
+        if self.partial.is_empty() {
+            return Ok(());
+        }
+        let invalid = InvalidUtf8 {
+            position: self.position,
+        };
+        self.position += self.partial.len() as u64;
+        self.partial.clear();
+        Err(invalid)
+    }
+}
+
+/// Converts a character of any variant, keeping it if it has no version in the variant.
+fn convert(c: char, variant: Variant) -> char {
+    crate::error::convert_char(c, variant).unwrap_or(c)
+}
+
+/// Length of the UTF-8 sequence starting with the byte.
+fn sequence_width(first: u8) -> usize {
+    match first {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
+    }
+}
+
+/// Writer converting UTF-8 text to a variant before passing it to the inner writer.
+///
+/// Sequences split between calls to [`write`](io::Write::write) are supported.
+///
+/// # Errors
+///
+/// Writing invalid UTF-8 fails with [`InvalidUtf8`], which gives the position of the sequence.
+/// Bytes before the sequence are converted and written.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use unicode_font::{stream::StyledWriter, Variant};
+/// let mut writer = StyledWriter::new(Vec::new(), Variant::Fraktur);
+/// let bytes = "abc".as_bytes();
+/// writer.write_all(&bytes[..2]).unwrap();
+/// writer.write_all(&bytes[2..]).unwrap();
+/// assert_eq!(writer.finish().unwrap(), "𝔞𝔟𝔠".as_bytes());
+/// ```
+#[derive(Debug)]
+pub struct StyledWriter<W: io::Write> {
+    inner: W,
+    decoder: Decoder,
+    buffer: String,
+}
+
+impl<W: io::Write> StyledWriter<W> {
+    /// Creates a writer converting to the variant.
+    pub fn new(inner: W, variant: Variant) -> Self {
+        StyledWriter {
+            inner,
+            decoder: Decoder::new(variant),
+            buffer: String::new(),
+        }
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Flushes the inner writer and returns it.
+    ///
+    /// # Errors
+    ///
+    /// If the text ended in the middle of a UTF-8 sequence, or flushing failed.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.decoder.finish()?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: io::Write> io::Write for StyledWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.clear();
+        let written = self
+            .decoder
+            .decode(buf, &mut self.buffer)
+            .map_err(|(invalid, _)| invalid)?;
+        self.inner.write_all(self.buffer.as_bytes())?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader converting the UTF-8 text of the inner reader to a variant.
+///
+/// # Errors
+///
+/// Reading invalid UTF-8 fails with [`InvalidUtf8`], which gives the position of the sequence.
+/// The invalid sequence is skipped, so that reading can go on.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use unicode_font::{stream::StyledReader, Variant};
+/// let mut reader = StyledReader::new("𝐁𝐨𝐥𝐝 text".as_bytes(), Variant::Plain);
+/// let mut text = String::new();
+/// reader.read_to_string(&mut text).unwrap();
+/// assert_eq!(text, "Bold text");
+/// ```
+#[derive(Debug)]
+pub struct StyledReader<R: io::Read> {
+    inner: R,
+    decoder: Decoder,
+    /// Bytes read from the inner reader.
+    input: Box<[u8]>,
+    /// Range of `input` still to decode.
+    start: usize,
+    end: usize,
+    /// Converted text not returned yet.
+    output: String,
+    returned: usize,
+}
+
+impl<R: io::Read> StyledReader<R> {
+    /// Creates a reader converting to the variant.
+    pub fn new(inner: R, variant: Variant) -> Self {
+        StyledReader {
+            inner,
+            decoder: Decoder::new(variant),
+            input: vec![0; 8 * 1024].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            output: String::new(),
+            returned: 0,
+        }
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader.
+    ///
+    /// Reading from it directly may corrupt the converted text.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, discarding buffered text.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> io::Read for StyledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.returned == self.output.len() {
+            self.output.clear();
+            self.returned = 0;
+            if self.start == self.end {
+                let read = self.inner.read(&mut self.input)?;
+                if read == 0 {
+                    self.decoder.finish()?;
+                    return Ok(0);
+                }
+                self.start = 0;
+                self.end = read;
+            }
+            match self
+                .decoder
+                .decode(&self.input[self.start..self.end], &mut self.output)
+            {
+                Ok(consumed) => self.start += consumed,
+                Err((invalid, skipped)) => {
+                    self.start += skipped;
+                    return Err(invalid.into());
+                }
+            }
+        }
+        let available = &self.output.as_bytes()[self.returned..];
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.returned += len;
+        Ok(len)
+    }
+}
+
+/// [`fmt::Write`] adapter converting text to a variant.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write;
+/// use unicode_font::{stream::StyledFmtWriter, Variant};
+/// let mut writer = StyledFmtWriter::new(String::new(), Variant::Monospace);
+/// write!(writer, "x = {}", 1).unwrap();
+/// assert_eq!(writer.into_inner(), "𝚡 = 𝟷");
+/// ```
+#[derive(Debug, Clone)]
+pub struct StyledFmtWriter<W: fmt::Write> {
+    inner: W,
+    variant: Variant,
+}
+
+impl<W: fmt::Write> StyledFmtWriter<W> {
+    /// Creates a writer converting to the variant.
+    pub fn new(inner: W, variant: Variant) -> Self {
+        StyledFmtWriter { inner, variant }
+    }
+
+    /// Returns the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for StyledFmtWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.chars()
+            .try_for_each(|c| self.inner.write_char(convert(c, self.variant)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    #[cfg(any(feature = "bold", feature = "fraktur"))]
+    use std::io::Write;
+
+    /// Reader returning one byte at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((first, rest)) if !buf.is_empty() => {
+                    buf[0] = *first;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn position(error: &io::Error) -> u64 {
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<InvalidUtf8>())
+            .unwrap()
+            .position
+    }
+
+    #[cfg(feature = "fraktur")]
+    #[test]
+    fn writer_handles_split_sequences() {
+        let mut writer = StyledWriter::new(Vec::new(), Variant::Plain);
+        for byte in "𝔞é b".as_bytes() {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), "aé b".as_bytes());
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn writer_reports_invalid_utf8() {
+        let mut writer = StyledWriter::new(Vec::new(), Variant::Bold);
+        let error = writer.write_all(b"ab\xffc").unwrap_err();
+        assert_eq!(position(&error), 2);
+        assert_eq!(writer.get_ref(), "𝐚𝐛".as_bytes());
+
+        let mut writer = StyledWriter::new(Vec::new(), Variant::Bold);
+        writer.write_all(&"a€".as_bytes()[..2]).unwrap();
+        assert_eq!(position(&writer.finish().unwrap_err()), 1);
+    }
+
+    #[cfg(all(feature = "bold", feature = "fraktur", feature = "italic"))]
+    #[test]
+    fn reader_handles_split_sequences() {
+        let mut reader = StyledReader::new(Trickle("a𝐛 ℭ".as_bytes()), Variant::Italic);
+        let mut text = String::new();
+        reader.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "𝑎𝑏 𝐶");
+    }
+
+    #[test]
+    fn reader_reports_invalid_utf8() {
+        let mut reader = StyledReader::new(Trickle(b"a\xe2\x82b\xff"), Variant::Plain);
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(position(&reader.read(&mut buf).unwrap_err()), 1);
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], b'b');
+        assert_eq!(position(&reader.read(&mut buf).unwrap_err()), 4);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn reader_with_small_buffers() {
+        let mut reader = StyledReader::new("ab".as_bytes(), Variant::Bold);
+        let mut buf = [0; 3];
+        let mut bytes = Vec::new();
+        loop {
+            let read = reader.read(&mut buf).unwrap();
+            if read == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..read]);
+        }
+        assert_eq!(bytes, "𝐚𝐛".as_bytes());
+    }
+}
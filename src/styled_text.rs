@@ -0,0 +1,292 @@
+//! Styled text as plain text with font variant spans.
+
+use crate::Variant;
+use core::ops::Range;
+
+/// Plain text together with the font variants applied to parts of it.
+///
+/// This is the common intermediate representation when converting between styled Unicode
+/// and markup formats, like [HTML](crate::html) or [ANSI escape codes](crate::ansi).
+///
+/// # Remarks
+///
+/// Spans are sorted, do not overlap and their ranges are byte ranges into [`StyledText::text`].
+/// Plain text has no span.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{StyledText, Variant};
+/// let mut text = StyledText::from_unicode("𝐇𝐞𝐥𝐥𝐨 world");
+/// assert_eq!(text.text(), "Hello world");
+/// text.apply(6..11, Variant::Italic);
+/// assert_eq!(text.to_unicode(), "𝐇𝐞𝐥𝐥𝐨 𝑤𝑜𝑟𝑙𝑑");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StyledText {
+    text: String,
+    spans: Vec<Span>,
+}
+
+/// Part of a [`StyledText`] in a font variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Byte range in the plain text.
+    pub range: Range<usize>,
+    /// Font variant of the range.
+    pub variant: Variant,
+}
+
+impl StyledText {
+    /// Creates text with no styling.
+    pub fn new(text: impl Into<String>) -> Self {
+        StyledText {
+            text: text.into(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Segments styled Unicode into plain text and spans.
+    ///
+    /// The text is split into [runs](crate::font_runs) of the same variant, which become spans,
+    /// so that `StyledText::from_unicode(s).to_unicode() == s` for any `s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::{Span, StyledText, Variant};
+    /// let text = StyledText::from_unicode("a𝐛𝐜");
+    /// assert_eq!(text.text(), "abc");
+    /// assert_eq!(text.spans(), &[Span { range: 1..3, variant: Variant::Bold }]);
+    /// ```
+    pub fn from_unicode(s: &str) -> Self {
+        let mut styled = StyledText::default();
+        for run in crate::font_runs(s).join_neutral(false) {
+            styled.push(&run.plain, run.variant);
+        }
+        styled
+    }
+
+    /// Renders the text as styled Unicode.
+    ///
+    /// Characters with no version in the variant of their span are left plain.
+    pub fn to_unicode(&self) -> String {
+        self.styled_chars()
+            .map(|(c, variant)| match variant {
+                Some(variant) => variant.convert(c).unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+
+    /// Plain text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Styled parts of the text, sorted by position.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Returns `true` if the text has no styling.
+    pub fn is_plain(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Iterates over the plain characters and the variant they are styled with.
+    pub fn styled_chars(&self) -> impl Iterator<Item = (char, Option<Variant>)> + '_ {
+        let mut spans = self.spans.iter().peekable();
+        self.text.char_indices().map(move |(i, c)| {
+            while spans.next_if(|span| span.range.end <= i).is_some() {}
+            let variant = spans
+                .peek()
+                .filter(|span| span.range.contains(&i))
+                .map(|span| span.variant);
+            (c, variant)
+        })
+    }
+
+    /// Variant of the character starting at byte `index`, if it is styled.
+    pub fn variant_at(&self, index: usize) -> Option<Variant> {
+        self.spans
+            .iter()
+            .find(|span| span.range.contains(&index))
+            .map(|span| span.variant)
+    }
+
+    /// Appends text in a variant, or with no styling if `variant` is `None`.
+    ///
+    /// The text is stored as is, it is not converted to plain.
+    /// The span is merged with the last one if they have the same variant and touch.
+    pub fn push(&mut self, text: &str, variant: Option<Variant>) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        let end = self.text.len();
+        match variant {
+            Some(variant) if variant != Variant::Plain && start < end => match self.spans.last_mut() {
+                Some(last) if last.variant == variant && last.range.end == start => last.range.end = end,
+                _ => self.spans.push(Span { range: start..end, variant }),
+            },
+            _ => (),
+        }
+    }
+
+    /// Styles a range of the text with a variant, replacing any previous styling.
+    ///
+    /// Applying [`Variant::Plain`] is the same as [clearing](StyledText::clear) the range.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on `char` boundaries.
+    pub fn apply(&mut self, range: Range<usize>, variant: Variant) {
+        self.clear(range.clone());
+        if variant == Variant::Plain || range.is_empty() {
+            return;
+        }
+        let position = self.spans.partition_point(|span| span.range.start < range.start);
+        self.spans.insert(position, Span { range, variant });
+    }
+
+    /// Removes the styling of a range of the text.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on `char` boundaries.
+    pub fn clear(&mut self, range: Range<usize>) {
+        self.check_range(&range);
+        if range.is_empty() {
+            return;
+        }
+        let mut spans = Vec::with_capacity(self.spans.len() + 1);
+        for span in self.spans.drain(..) {
+            if span.range.end <= range.start || range.end <= span.range.start {
+                spans.push(span);
+                continue;
+            }
+            if span.range.start < range.start {
+                spans.push(Span {
+                    range: span.range.start..range.start,
+                    variant: span.variant,
+                });
+            }
+            if range.end < span.range.end {
+                spans.push(Span {
+                    range: range.end..span.range.end,
+                    variant: span.variant,
+                });
+            }
+        }
+        self.spans = spans;
+    }
+
+    /// Merges spans of the same variant that touch each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::{StyledText, Variant};
+    /// let mut text = StyledText::new("abc");
+    /// text.apply(0..1, Variant::Bold);
+    /// text.apply(1..3, Variant::Bold);
+    /// assert_eq!(text.spans().len(), 2);
+    /// text.merge_adjacent();
+    /// assert_eq!(text.spans().len(), 1);
+    /// ```
+    pub fn merge_adjacent(&mut self) {
+        let mut spans: Vec<Span> = Vec::with_capacity(self.spans.len());
+        for span in self.spans.drain(..) {
+            match spans.last_mut() {
+                Some(last) if last.variant == span.variant && last.range.end == span.range.start => {
+                    last.range.end = span.range.end
+                }
+                _ => spans.push(span),
+            }
+        }
+        self.spans = spans;
+    }
+
+    fn check_range(&self, range: &Range<usize>) {
+        assert!(
+            range.start <= range.end
+                && self.text.is_char_boundary(range.start)
+                && self.text.is_char_boundary(range.end),
+            "range {range:?} is not a valid range of the text {:?}",
+            self.text
+        );
+    }
+}
+
+impl From<&str> for StyledText {
+    fn from(s: &str) -> Self {
+        StyledText::from_unicode(s)
+    }
+}
+
+impl core::fmt::Display for StyledText {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.to_unicode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(
+        feature = "extension",
+        feature = "arabic_mathematical_initial",
+        feature = "bold",
+        feature = "circled",
+        feature = "double_struck",
+        feature = "fraktur",
+        feature = "italic",
+        feature = "script",
+        feature = "subscript",
+        feature = "superscript"
+    ))]
+    #[test]
+    fn from_unicode_is_lossless() {
+        for s in ["", "plain", "𝐁𝐨𝐥𝐝 and 𝑖𝑡𝑎𝑙𝑖𝑐!", "ⓐ¹₂ 𝔣𝔯𝔞𝔨 ℎ ℂ", "ابج 𞸡"] {
+            assert_eq!(StyledText::from_unicode(s).to_unicode(), s);
+        }
+    }
+
+    #[cfg(all(feature = "bold", feature = "italic"))]
+    #[test]
+    fn apply_splits_spans() {
+        let mut text = StyledText::from_unicode("𝐚𝐛𝐜𝐝");
+        text.apply(1..3, Variant::Italic);
+        assert_eq!(text.to_unicode(), "𝐚𝑏𝑐𝐝");
+        assert_eq!(
+            text.spans(),
+            &[
+                Span { range: 0..1, variant: Variant::Bold },
+                Span { range: 1..3, variant: Variant::Italic },
+                Span { range: 3..4, variant: Variant::Bold },
+            ]
+        );
+        text.apply(0..4, Variant::Plain);
+        assert!(text.is_plain());
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn clear_and_merge() {
+        let mut text = StyledText::new("abcd");
+        text.apply(2..4, Variant::Bold);
+        text.apply(0..2, Variant::Bold);
+        text.merge_adjacent();
+        assert_eq!(text.spans(), &[Span { range: 0..4, variant: Variant::Bold }]);
+        text.clear(1..2);
+        assert_eq!(text.to_unicode(), "𝐚b𝐜𝐝");
+        assert_eq!(text.variant_at(1), None);
+        assert_eq!(text.variant_at(3), Some(Variant::Bold));
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_inside_char() {
+        StyledText::new("é").apply(0..1, Variant::Bold);
+    }
+}
@@ -0,0 +1,352 @@
+//! Transformations of styled text.
+
+use crate::{ConversionError, Variant};
+use std::collections::BTreeMap;
+use strum::IntoEnumIterator;
+
+/// Result of transforming a text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Transformed {
+    /// Transformed text.
+    pub output: String,
+    /// Input characters that could not keep their variant, or take the requested one.
+    pub unconverted: Vec<ConversionError>,
+}
+
+impl Transformed {
+    /// Returns `true` if every character kept its variant.
+    pub fn is_complete(&self) -> bool {
+        self.unconverted.is_empty()
+    }
+}
+
+/// Applies a character transformation while preserving the font variant of each character.
+///
+/// Each character is decomposed into its variant and plain version.
+/// The transformation is applied to the plain version, and the result converted back to the variant.
+/// If the result has no version in that variant, the plain result is used and the character is reported.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::transform::map_preserving_font;
+/// let shifted = map_preserving_font("𝐚𝐛 ⓨⓩ", |c| match c {
+///     'a'..='y' => (c as u8 + 1) as char,
+///     _ => c,
+/// });
+/// assert_eq!(shifted.output, "𝐛𝐜 ⓩⓩ");
+/// ```
+pub fn map_preserving_font(s: &str, f: impl Fn(char) -> char) -> Transformed {
+    let mut output = String::with_capacity(s.len());
+    let mut unconverted = Vec::new();
+    for (i, c) in s.char_indices() {
+        match Variant::detect(&c) {
+            Some(variant) if variant != Variant::Plain => {
+                let plain = f(Variant::Plain.convert(c).unwrap_or(c));
+                match variant.convert(plain) {
+                    Some(styled) => output.push(styled),
+                    None => {
+                        output.push(plain);
+                        unconverted.push(ConversionError {
+                            index: i,
+                            char: c,
+                            variant,
+                            reason: crate::error::missing_form(plain, variant),
+                        });
+                    }
+                }
+            }
+            _ => output.push(f(c)),
+        }
+    }
+    Transformed {
+        output,
+        unconverted,
+    }
+}
+
+/// Returns the uppercase equivalent of the text, preserving fonts.
+///
+/// Characters whose uppercase is longer than one `char`, like `'ß'`, are kept.
+/// Characters whose uppercase has no version in their variant are converted to plain uppercase.
+/// Use [`map_preserving_font`] to know which characters these are.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::transform::to_uppercase_styled;
+/// assert_eq!("𝐡𝐞𝐥𝐥𝐨".to_uppercase(), "𝐡𝐞𝐥𝐥𝐨");
+/// assert_eq!(to_uppercase_styled("𝐡𝐞𝐥𝐥𝐨, é"), "𝐇𝐄𝐋𝐋𝐎, É");
+/// ```
+pub fn to_uppercase_styled(s: &str) -> String {
+    map_preserving_font(s, |c| single(c.to_uppercase()).unwrap_or(c)).output
+}
+
+/// Returns the lowercase equivalent of the text, preserving fonts.
+///
+/// See [`to_uppercase_styled`] for characters that can not keep their variant.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::transform::to_lowercase_styled;
+/// assert_eq!(to_lowercase_styled("𝔄𝔅ℭ"), "𝔞𝔟𝔠");
+/// ```
+pub fn to_lowercase_styled(s: &str) -> String {
+    map_preserving_font(s, |c| single(c.to_lowercase()).unwrap_or(c)).output
+}
+
+/// Counts the characters of each variant, plain included.
+///
+/// Neutral characters, those absent from every map, are not counted.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{transform::variant_histogram, Variant};
+/// let histogram = variant_histogram("𝐁𝐨𝐥𝐝 and 𝐭𝐞𝐱𝐭");
+/// assert_eq!(histogram[&Variant::Bold], 8);
+/// assert_eq!(histogram[&Variant::Plain], 3);
+/// ```
+pub fn variant_histogram(s: &str) -> BTreeMap<Variant, usize> {
+    let mut histogram = BTreeMap::new();
+    for variant in s.chars().filter_map(|c| Variant::detect(&c)) {
+        *histogram.entry(variant).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Returns the most common styled variant of the text.
+///
+/// Plain characters are not taken into account.
+/// Ties are broken in favour of the first variant in [`Variant::iter`](strum::IntoEnumIterator::iter) order.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{transform::dominant_variant, Variant};
+/// assert_eq!(dominant_variant("𝐁𝐨𝐥𝐝 and 𝐭𝐞𝐱𝐭"), Some(Variant::Bold));
+/// assert_eq!(dominant_variant("plain"), None);
+/// ```
+pub fn dominant_variant(s: &str) -> Option<Variant> {
+    variant_histogram(s)
+        .into_iter()
+        .filter(|(variant, _)| variant != &Variant::Plain)
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(variant, _)| variant)
+}
+
+/// Converts the characters of one variant into another variant.
+///
+/// If `from` is `None`, all styled characters are converted.
+/// Characters with no version in the `to` variant are left unchanged and reported.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{transform::restyle_str, Variant};
+/// let restyled = restyle_str("𝐁𝐨𝐥𝐝 and 𝑖𝑡", Some(Variant::Bold), Variant::SansSerifBold);
+/// assert_eq!(restyled.output, "𝗕𝗼𝗹𝗱 and 𝑖𝑡");
+/// let restyled = restyle_str("𝐁𝐨𝐥𝐝 and 𝑖𝑡", None, Variant::SansSerifBold);
+/// assert_eq!(restyled.output, "𝗕𝗼𝗹𝗱 and 𝗶𝘁");
+/// ```
+pub fn restyle_str(s: &str, from: Option<Variant>, to: Variant) -> Transformed {
+    let mut output = String::with_capacity(s.len());
+    let mut unconverted = Vec::new();
+    for (i, c) in s.char_indices() {
+        let selected = match (Variant::detect(&c), from) {
+            (Some(variant), Some(from)) => variant == from,
+            (Some(variant), None) => variant != Variant::Plain,
+            (None, _) => false,
+        };
+        if !selected {
+            output.push(c);
+            continue;
+        }
+        let plain = Variant::Plain.convert(c).unwrap_or(c);
+        match to.convert(plain) {
+            Some(styled) => output.push(styled),
+            None => {
+                output.push(c);
+                unconverted.push(ConversionError {
+                    index: i,
+                    char: c,
+                    variant: to,
+                    reason: crate::error::missing_form(plain, to),
+                });
+            }
+        }
+    }
+    Transformed {
+        output,
+        unconverted,
+    }
+}
+
+/// Conversion of a text into one variant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VariantResult {
+    /// Variant the text was converted to.
+    pub variant: Variant,
+    /// Converted text.
+    pub output: String,
+    /// Input characters with no version in the variant.
+    ///
+    /// These characters are left unchanged in the output.
+    pub unconverted: Vec<ConversionError>,
+}
+
+impl VariantResult {
+    /// Returns `true` if every supported character was converted.
+    pub fn is_complete(&self) -> bool {
+        self.unconverted.is_empty()
+    }
+}
+
+/// Converts a text into every variant.
+///
+/// Characters are converted to plain before converting them to each variant,
+/// so styled input is supported.
+/// Neutral characters, those absent from every map like spaces, are left unchanged and not reported.
+///
+/// Results are sorted by completeness, i.e. by the number of unconverted characters,
+/// and then in the order of [`Variant::iter`](strum::IntoEnumIterator::iter).
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{all_variants, Variant};
+/// let results = all_variants("AB 1");
+/// let bold = results.iter().find(|result| result.variant == Variant::Bold).unwrap();
+/// assert_eq!(bold.output, "𝐀𝐁 𝟏");
+/// assert!(results[0].is_complete());
+/// let small_capital = results.iter().find(|result| result.variant == Variant::SmallCapital).unwrap();
+/// assert_eq!(small_capital.output, "ᴀʙ 1");
+/// assert_eq!(small_capital.unconverted[0].index, 3);
+/// assert_eq!(small_capital.unconverted[0].char, '1');
+/// ```
+pub fn all_variants(s: &str) -> Vec<VariantResult> {
+    let mut results: Vec<_> = Variant::iter()
+        .map(|variant| {
+            let mut output = String::with_capacity(s.len());
+            let mut unconverted = Vec::new();
+            for (i, c) in s.char_indices() {
+                if Variant::Plain.convert(c).is_none() {
+                    output.push(c);
+                    continue;
+                }
+                match crate::error::convert_char(c, variant) {
+                    Ok(converted) => output.push(converted),
+                    Err(reason) => {
+                        output.push(c);
+                        unconverted.push(ConversionError {
+                            index: i,
+                            char: c,
+                            variant,
+                            reason,
+                        });
+                    }
+                }
+            }
+            VariantResult {
+                variant,
+                output,
+                unconverted,
+            }
+        })
+        .collect();
+    results.sort_by_key(|result| result.unconverted.len());
+    results
+}
+
+/// Returns the only item of the iterator, if it has exactly one.
+fn single(mut iter: impl Iterator<Item = char>) -> Option<char> {
+    match (iter.next(), iter.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(all(feature = "bold", feature = "mathematical_script", feature = "sans_serif"))]
+    #[test]
+    fn case_conversion_keeps_variant() {
+        assert_eq!(to_uppercase_styled("𝖺𝖻𝖼 𝒶"), "𝖠𝖡𝖢 𝒜");
+        assert_eq!(to_lowercase_styled("𝐀𝐁 Ⓐ"), "𝐚𝐛 ⓐ");
+        assert_eq!(to_uppercase_styled("ß"), "ß");
+    }
+
+    #[cfg(feature = "double_struck_italic")]
+    #[test]
+    fn missing_forms_are_reported() {
+        // Double-struck italic has no capital E
+        let transformed = map_preserving_font("ⅇb", |c| c.to_ascii_uppercase());
+        assert_eq!(transformed.output, "EB");
+        assert_eq!(
+            transformed.unconverted,
+            vec![ConversionError {
+                index: 0,
+                char: 'ⅇ',
+                variant: Variant::DoubleStruckItalic,
+                reason: crate::error::Reason::NoFormInVariant,
+            }]
+        );
+        assert!(!transformed.is_complete());
+    }
+
+    #[cfg(all(feature = "bold", feature = "italic"))]
+    #[test]
+    fn dominant_variant_ties() {
+        assert_eq!(dominant_variant("𝐚𝑏"), Some(Variant::Bold));
+        assert_eq!(dominant_variant("𝐚𝑏𝑐"), Some(Variant::Italic));
+        assert_eq!(dominant_variant(""), None);
+    }
+
+    #[cfg(all(feature = "bold", feature = "fraktur"))]
+    #[test]
+    fn restyle_plain_and_unconvertible() {
+        let restyled = restyle_str("ab 𝐜", Some(Variant::Plain), Variant::Fraktur);
+        assert_eq!(restyled.output, "𝔞𝔟 𝐜");
+        let restyled = restyle_str("𝐀𝐁𝐂", None, Variant::Fraktur);
+        assert_eq!(restyled.output, "𝔄𝔅ℭ");
+        let restyled = restyle_str("𝐚𝟏", None, Variant::Fraktur);
+        assert_eq!(restyled.output, "𝔞𝟏");
+        assert_eq!(
+            restyled.unconverted,
+            vec![ConversionError {
+                index: 4,
+                char: '𝟏',
+                variant: Variant::Fraktur,
+                reason: crate::error::Reason::NoFormInVariant,
+            }]
+        );
+        assert_eq!(restyle_str("𝐚𝔟", None, Variant::Plain).output, "ab");
+    }
+
+    #[test]
+    fn all_variants_are_sorted_by_completeness() {
+        let results = all_variants("ab");
+        assert_eq!(results.len(), Variant::iter().count());
+        assert!(results
+            .windows(2)
+            .all(|pair| pair[0].unconverted.len() <= pair[1].unconverted.len()));
+    }
+
+    #[cfg(feature = "bold")]
+    #[test]
+    fn complete_variants_come_first() {
+        let results = all_variants("ab");
+        assert!(results[0].unconverted.is_empty());
+        assert!(!results.last().unwrap().unconverted.is_empty());
+        let plain = results
+            .iter()
+            .find(|result| result.variant == Variant::Plain)
+            .unwrap();
+        assert_eq!(plain.output, "ab");
+        assert_eq!(all_variants("𝐚")[0].output, "𝐚");
+    }
+}
@@ -1,11 +1,13 @@
 //! Font variants.
 
+use strum::IntoEnumIterator;
+
 /// Font variants we deal with.
 ///
 /// # Main feature
 ///
 /// Implements `FromString` to parse a variant from the UNICODE name in English.
-#[derive(Debug, strum::Display, strum::EnumString, strum::EnumIter, Clone, Copy)]
+#[derive(Debug, strum::Display, strum::EnumString, strum::EnumIter, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Variant {
     #[strum(ascii_case_insensitive)]
     ArabicMathematical,
@@ -85,6 +87,99 @@ pub enum Variant {
     Wide,
 }
 
+/// Set of font variants.
+///
+/// # Examples
+///
+/// ```
+/// use unicode_font::{Variant, VariantSet};
+/// let set: VariantSet = [Variant::Bold, Variant::Italic].into_iter().collect();
+/// assert!(set.contains(Variant::Bold));
+/// assert!(!set.contains(Variant::Fraktur));
+/// assert_eq!(set.len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct VariantSet(u64);
+
+impl VariantSet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        VariantSet(0)
+    }
+
+    /// Creates a set with all variants.
+    pub fn all() -> Self {
+        Variant::iter().collect()
+    }
+
+    fn bit(variant: Variant) -> u64 {
+        1 << variant as u64
+    }
+
+    /// Adds a variant to the set.
+    ///
+    /// Returns whether the variant was newly inserted.
+    pub fn insert(&mut self, variant: Variant) -> bool {
+        let new = !self.contains(variant);
+        self.0 |= Self::bit(variant);
+        new
+    }
+
+    /// Removes a variant from the set.
+    ///
+    /// Returns whether the variant was present in the set.
+    pub fn remove(&mut self, variant: Variant) -> bool {
+        let present = self.contains(variant);
+        self.0 &= !Self::bit(variant);
+        present
+    }
+
+    /// Returns `true` if the set contains the variant.
+    pub fn contains(&self, variant: Variant) -> bool {
+        self.0 & Self::bit(variant) != 0
+    }
+
+    /// Number of variants in the set.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns `true` if the set contains no variants.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the variants of the set, in the order of [`Variant::iter`].
+    pub fn iter(&self) -> impl Iterator<Item = Variant> {
+        let set = *self;
+        Variant::iter().filter(move |variant| set.contains(*variant))
+    }
+}
+
+impl From<Variant> for VariantSet {
+    fn from(variant: Variant) -> Self {
+        let mut set = VariantSet::new();
+        set.insert(variant);
+        set
+    }
+}
+
+impl FromIterator<Variant> for VariantSet {
+    fn from_iter<I: IntoIterator<Item = Variant>>(iter: I) -> Self {
+        let mut set = VariantSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Variant> for VariantSet {
+    fn extend<I: IntoIterator<Item = Variant>>(&mut self, iter: I) {
+        for variant in iter {
+            self.insert(variant);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ParseError {
     pub name: String,
@@ -97,7 +192,191 @@ impl core::fmt::Display for ParseError {
 }
 impl std::error::Error for ParseError {}
 
+/// Implements the access to the map of each variant.
+///
+/// Variants whose feature is disabled have empty maps.
+macro_rules! variant_maps {
+    ( $( $variant: ident => $module: ident : $feature: literal ),* ) => {
+        paste::paste! {
+            impl Variant {
+                /// Mapping used to convert plain characters into this variant.
+                ///
+                /// For [`Variant::Plain`], this is [`PLAIN_MAP`](crate::plain::PLAIN_MAP),
+                /// which maps every supported character to its plain version.
+                ///
+                /// The map is empty if the cargo feature of the variant is disabled.
+                pub fn map(&self) -> &'static phf::Map<char, char> {
+                    match self {
+                        Variant::Plain => &crate::plain::PLAIN_MAP,
+                        $(
+                            #[cfg(feature = $feature)]
+                            Variant::$variant => &crate::$module::[< $module:upper _MAP >],
+                        )*
+                        #[allow(unreachable_patterns)]
+                        _ => &EMPTY_MAP,
+                    }
+                }
+
+                /// Keys of the map only supported with our extension, used to explain failures when it is turned off.
+                #[cfg(not(feature = "extension"))]
+                pub(crate) fn extension_only(&self) -> &'static [(char, char)] {
+                    match self {
+                        Variant::Plain => crate::extension_only::PLAIN,
+                        $( Variant::$variant => crate::extension_only::[< $module:upper >], )*
+                    }
+                }
+
+                /// Same data as [`Variant::map`], as sorted runs of `(first, last, target)` characters.
+                const fn ranges(&self) -> &'static [(char, char, char)] {
+                    match self {
+                        Variant::Plain => crate::ranges::PLAIN,
+                        $( Variant::$variant => crate::ranges::[< $module:upper >], )*
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Map of the variants whose feature is disabled.
+static EMPTY_MAP: phf::Map<char, char> = phf::Map::new();
+
+variant_maps!(
+    ArabicMathematical => arabic_mathematical: "arabic_mathematical",
+    ArabicMathematicalInitial => arabic_mathematical_initial: "arabic_mathematical_initial",
+    Bold => bold: "bold",
+    BoldItalic => bold_italic: "bold_italic",
+    BoldFraktur => bold_fraktur: "bold_fraktur",
+    BoldScript => bold_script: "bold_script",
+    Circled => circled: "circled",
+    Comma => comma: "comma",
+    DoubleStruck => double_struck: "double_struck",
+    DoubleStruckItalic => double_struck_italic: "double_struck_italic",
+    Fraktur => fraktur: "fraktur",
+    FullStop => full_stop: "full_stop",
+    Fullwidth => fullwidth: "fullwidth",
+    Italic => italic: "italic",
+    Looped => looped: "looped",
+    Monospace => monospace: "monospace",
+    NegativeCircled => negative_circled: "negative_circled",
+    NegativeSquared => negative_squared: "negative_squared",
+    Regional => regional: "regional",
+    Segmented => segmented: "segmented",
+    Other => other: "other",
+    Parenthesized => parenthesized: "parenthesized",
+    SansSerifBoldItalic => sans_serif_bold_italic: "sans_serif_bold_italic",
+    SansSerifBold => sans_serif_bold: "sans_serif_bold",
+    SansSerifItalic => sans_serif_italic: "sans_serif_italic",
+    SansSerif => sans_serif: "sans_serif",
+    MathematicalScript => mathematical_script: "mathematical_script",
+    Script => script: "script",
+    SmallCapital => small_capital: "small_capital",
+    Superscript => superscript: "superscript",
+    Subscript => subscript: "subscript",
+    Stretched => stretched: "stretched",
+    Squared => squared: "squared",
+    Tailed => tailed: "tailed",
+    Wide => wide: "wide"
+);
+
 impl Variant {
+    /// Returns the variant version of a character if there is any.
+    ///
+    /// This is the same as calling the corresponding `try_as_*` function.
+    /// It looks up a hash map, which holds the returned reference;
+    /// [`Variant::convert`] returns the character by value and is faster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::Variant;
+    /// assert_eq!(Variant::Bold.try_as(&'a'), Some(&'\u{1D41A}')); // 𝐚
+    /// assert_eq!(Variant::Plain.try_as(&'\u{1D41A}'), Some(&'a'));
+    /// ```
+    pub fn try_as(&self, c: &char) -> Option<&'static char> {
+        self.map().get(c)
+    }
+
+    /// Returns the variant version of a character by value.
+    ///
+    /// This is equivalent to [`Variant::try_as`], but looks up sorted ranges instead of a hash map,
+    /// which is faster when converting a lot of text and can be used in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::Variant;
+    /// assert_eq!(Variant::DoubleStruck.convert('B'), Some('\u{1D539}')); // 𝔹
+    /// assert_eq!(Variant::DoubleStruck.convert('C'), Some('\u{2102}')); // ℂ
+    /// assert_eq!(Variant::DoubleStruck.convert('\u{1D539}'), None);
+    /// ```
+    pub const fn convert(&self, c: char) -> Option<char> {
+        crate::ranges::lookup(self.ranges(), c)
+    }
+
+    /// Converts a text of any variant into this variant.
+    ///
+    /// Whitespace is kept as is.
+    ///
+    /// # Errors
+    ///
+    /// If any other character can not be converted, with a report of every such character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::Variant;
+    /// assert_eq!(Variant::Bold.try_as_str("𝔥𝔦 there").unwrap(), "𝐡𝐢 𝐭𝐡𝐞𝐫𝐞");
+    /// let report = Variant::Bold.try_as_str("1 €").unwrap_err();
+    /// assert_eq!(report.errors[0].index, 2);
+    /// ```
+    pub fn try_as_str(&self, s: &str) -> Result<String, crate::ConversionReport> {
+        let mut output = String::with_capacity(s.len());
+        let mut errors = Vec::new();
+        for (index, c) in s.char_indices() {
+            if c.is_whitespace() {
+                output.push(c);
+                continue;
+            }
+            match crate::error::convert_char(c, *self) {
+                Ok(converted) => output.push(converted),
+                Err(reason) => errors.push(crate::ConversionError {
+                    index,
+                    char: c,
+                    variant: *self,
+                    reason,
+                }),
+            }
+        }
+        if errors.is_empty() {
+            Ok(output)
+        } else {
+            Err(crate::ConversionReport::new(s, errors))
+        }
+    }
+
+    /// Detects the variant of a character.
+    ///
+    /// Returns `None` if the character is not supported, i.e. it has no plain version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use unicode_font::Variant;
+    /// assert_eq!(Variant::detect(&'\u{1D41A}'), Some(Variant::Bold)); // 𝐚
+    /// assert_eq!(Variant::detect(&'a'), Some(Variant::Plain));
+    /// assert_eq!(Variant::detect(&' '), None);
+    /// ```
+    pub fn detect(c: &char) -> Option<Self> {
+        let plain = Variant::Plain.convert(*c)?;
+        if plain == *c {
+            return Some(Variant::Plain);
+        }
+        Variant::iter()
+            .filter(|variant| variant != &Variant::Plain)
+            .find(|variant| variant.convert(plain) == Some(*c))
+    }
+
     /// Converts a Unicode name into a Variant.
     ///
     /// # Errors
@@ -219,3 +498,51 @@ impl Variant {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_match_maps() {
+        for variant in Variant::iter() {
+            let len: u32 = variant
+                .ranges()
+                .iter()
+                .map(|(first, last, _)| *last as u32 - *first as u32 + 1)
+                .sum();
+            assert_eq!(len as usize, variant.map().len(), "{variant}");
+            for (c, styled) in variant.map().entries() {
+                assert_eq!(variant.convert(*c), Some(*styled), "{variant} {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn detect_inverts_try_as() {
+        for variant in Variant::iter().filter(|v| v != &Variant::Plain) {
+            for (plain, styled) in variant.map().entries() {
+                if plain != styled {
+                    assert_eq!(Variant::detect(styled), Some(variant), "failed for {styled}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn variant_set_operations() {
+        let mut set = VariantSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(Variant::Wide));
+        assert!(!set.insert(Variant::Wide));
+        assert!(set.insert(Variant::ArabicMathematical));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Variant::ArabicMathematical, Variant::Wide]
+        );
+        assert!(set.remove(Variant::Wide));
+        assert!(!set.remove(Variant::Wide));
+        assert_eq!(set, VariantSet::from(Variant::ArabicMathematical));
+        assert_eq!(VariantSet::all().len(), Variant::iter().count());
+    }
+}